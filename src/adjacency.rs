@@ -0,0 +1,134 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module provides the neighbourhood and degree queries on graphs. They
+//! are backed by an adjacency index which is only built (once) when it is
+//! first needed.
+
+use crate::{Graph, Vertex};
+
+/// The adjacency index of a graph: for each vertex `v`, the entry `v-1` of
+/// each list gives the (sorted) neighbours of `v`. The predecessors and the
+/// union of both neighbourhoods are only stored for digraphs.
+#[derive(Debug, Clone)]
+pub(crate) struct Adjacency {
+    succ: Vec<Vec<Vertex>>,
+    pred: Vec<Vec<Vertex>>,
+    both: Vec<Vec<Vertex>>
+}
+impl Adjacency {
+    fn new<W>(g: &Graph<W>) -> Self {
+        let mut succ = vec![vec![]; g.n];
+        let mut pred = vec![];
+        let mut both = vec![];
+        if g.meta.digraph {
+            pred = vec![vec![]; g.n];
+            for e in g.list.keys() {
+                succ[e.src.id as usize - 1].push(e.dst);
+                pred[e.dst.id as usize - 1].push(e.src);
+            }
+            both = succ.iter().zip(pred.iter())
+                .map(|(s, p)| s.iter().chain(p.iter()).copied().collect())
+                .collect();
+        } else {
+            for e in g.list.keys() {
+                succ[e.src.id as usize - 1].push(e.dst);
+                if !e.is_self_loop() {
+                    succ[e.dst.id as usize - 1].push(e.src);
+                }
+            }
+        }
+        for list in succ.iter_mut().chain(pred.iter_mut()).chain(both.iter_mut()) {
+            list.sort_unstable();
+            list.dedup();
+        }
+        Adjacency{succ, pred, both}
+    }
+}
+
+impl<W> Graph<W> {
+    /// Returns the adjacency index of the graph (building it if needed)
+    fn adjacency(&self) -> &Adjacency {
+        self.adj.get_or_init(|| Adjacency::new(self))
+    }
+    /// Returns the (sorted) vertices adjacent to `v`. In a digraph, these are
+    /// both the successors and the predecessors of `v`.
+    pub fn neighbors(&self, v: Vertex) -> &[Vertex] {
+        let adj = self.adjacency();
+        if self.meta.digraph {
+            &adj.both[v.id as usize - 1]
+        } else {
+            &adj.succ[v.id as usize - 1]
+        }
+    }
+    /// Returns the (sorted) successors of `v`: the vertices `u` such that the
+    /// graph has an edge `v -> u`. This is `neighbors(v)` in an undirected
+    /// graph.
+    pub fn out_neighbors(&self, v: Vertex) -> &[Vertex] {
+        &self.adjacency().succ[v.id as usize - 1]
+    }
+    /// Returns the (sorted) predecessors of `v`: the vertices `u` such that
+    /// the graph has an edge `u -> v`. This is `neighbors(v)` in an
+    /// undirected graph.
+    pub fn in_neighbors(&self, v: Vertex) -> &[Vertex] {
+        let adj = self.adjacency();
+        if self.meta.digraph {
+            &adj.pred[v.id as usize - 1]
+        } else {
+            &adj.succ[v.id as usize - 1]
+        }
+    }
+
+    /// Returns the degree of `v`: the number of edges incident to `v` where
+    /// a self loop counts twice. In a digraph, this is the sum of the in and
+    /// out degrees of `v`.
+    pub fn degree(&self, v: Vertex) -> usize {
+        if self.meta.digraph {
+            self.out_degree(v) + self.in_degree(v)
+        } else {
+            let neighbours = self.neighbors(v);
+            neighbours.len() + neighbours.binary_search(&v).map_or(0, |_| 1)
+        }
+    }
+    /// Returns the number of edges leaving `v` (its degree in an undirected
+    /// graph)
+    pub fn out_degree(&self, v: Vertex) -> usize {
+        if self.meta.digraph {
+            self.out_neighbors(v).len()
+        } else {
+            self.degree(v)
+        }
+    }
+    /// Returns the number of edges entering `v` (its degree in an undirected
+    /// graph)
+    pub fn in_degree(&self, v: Vertex) -> usize {
+        if self.meta.digraph {
+            self.in_neighbors(v).len()
+        } else {
+            self.degree(v)
+        }
+    }
+    /// Returns the degrees of all vertices sorted in non-increasing order
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = self.vertices().map(|v| self.degree(v)).collect::<Vec<usize>>();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module lets the edges of a graph carry named attributes (e.g. a
+//! capacity and a cost, or a timestamp) on top of their weight. Each of
+//! these attributes is kept in its own edge map, and is written by the
+//! formats which support it (json, gml and csv).
+
+use crate::{Edge, EdgeMap, Graph, Vertex};
+use rand::distributions::{Distribution, Uniform};
+use std::collections::BTreeMap;
+
+/// The additional attributes of the edges (indexed by their names)
+pub(crate) type EdgeAttributes = BTreeMap<String, EdgeMap>;
+
+impl<W> Graph<W> {
+    /// Returns the names of the additional edge attributes (in alphabetical order)
+    pub fn attribute_names(&self) -> impl Iterator<Item=&str> {
+        self.attrs.keys().map(|name| name.as_str())
+    }
+    /// Returns the value of the named attribute of the given edge, if the
+    /// edge belongs to the graph and has such an attribute
+    pub fn attribute(&self, edge: Edge, name: &str) -> Option<isize> {
+        let edge = self.stored(edge)?;
+        self.attrs.get(name).and_then(|values| values.get(&edge).copied())
+    }
+    /// Sets the named attribute of the edge `src -> dst` and returns its
+    /// previous value. Nothing happens when the edge does not belong to the
+    /// graph.
+    pub fn set_attribute(&mut self, src: Vertex, dst: Vertex, name: &str, value: isize) -> Option<isize> {
+        let edge = self.stored(Edge{src, dst})?;
+        self.attrs.entry(name.to_string()).or_default().insert(edge, value)
+    }
+    /// Gives the named attribute to each edge, with a value picked at random
+    /// among the candidates (nothing happens when there is no candidate)
+    pub fn pluck_random_attribute(&mut self, name: &str, from: &[isize]) {
+        if from.is_empty() {
+            return;
+        }
+        let mut rng = crate::random::weight_rng();
+        let dist= Uniform::new(0, from.len());
+
+        let values = self.list.keys()
+            .map(|e| (*e, from[dist.sample(&mut rng)]))
+            .collect();
+        self.attrs.insert(name.to_string(), values);
+    }
+}
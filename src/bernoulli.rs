@@ -0,0 +1,209 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+
+//! This module implements the classic G(n, p) model where each candidate edge
+//! is included independently with likelihood p, so that the number of edges
+//! of the generated graphs follows a binomial distribution rather than being
+//! fixed to `round(p N)` (which is G(n, m) in disguise). The sparse graphs
+//! of an unconstrained model are generated in linear time with the geometric
+//! skipping of Batagelj and Brandes.
+
+use crate::{Edge, ErGenerator, ErModel, Graph, Vertex};
+use rand::Rng;
+
+impl ErModel {
+    /// The largest likelihood for which the graphs of an unconstrained
+    /// G(n, p) model are generated with geometric skipping
+    pub const SPARSE_P: f64 = 0.1;
+
+    /// Includes each candidate edge independently with likelihood p (and
+    /// each loop with its own likelihood, if it has one). When the model has
+    /// no constraint and p is at most `SPARSE_P`, the candidate edges are
+    /// enumerated in order while jumping over the non-edges (the length of
+    /// each jump follows a geometric distribution), which takes `O(n + m)`
+    /// time and no duplicate check. Otherwise, the number of edges is drawn
+    /// from the binomial distribution `B(N, p)` first, and that many edges
+    /// are then picked uniformly at random, which yields the very same
+    /// distribution over the graphs. The constraints of the model apply as
+    /// usual.
+    pub fn bernoulli(self) -> Self {
+        ErModel{bernoulli: true, ..self}
+    }
+    /// returns true iff the graphs are generated with geometric skipping
+    pub(crate) fn skips(self) -> bool {
+        self.bernoulli && self.p <= Self::SPARSE_P && !self.has_constraints()
+    }
+}
+
+impl<R: Rng> ErGenerator<R> {
+    /// Returns the number of random edges to pick in the next graph: it is
+    /// drawn from a binomial distribution in G(n, p) mode, and fixed otherwise
+    pub(crate) fn draw_nb_edges(&mut self) -> usize {
+        if self.model.bernoulli {
+            binomial(&mut self.rng, self.model.nb_possible_edges(), self.model.p)
+        } else {
+            self.model.nb_edges_to_pick()
+        }
+    }
+    /// Returns the number of loops to pick on their own in the next graph
+    /// (when they have a likelihood of their own)
+    pub(crate) fn draw_nb_loops(&mut self) -> usize {
+        match self.model.loop_p {
+            Some(q) if self.model.bernoulli => binomial(&mut self.rng, self.model.n as u128, q),
+            _ => self.model.nb_loops_to_pick()
+        }
+    }
+    /// Samples a sparse G(n, p) graph with the geometric skipping of
+    /// Batagelj and Brandes (see `ErModel::bernoulli`). The loops having a
+    /// likelihood of their own are skipped through on their own.
+    pub(crate) fn sample_sparse(&mut self) -> Graph {
+        let model    = self.model;
+        let mut g    = Graph::empty(model.into(), model.n);
+        let mut rows = Rows::new(model);
+        g.list.reserve(model.nb_edges_to_pick());
+        skip(&mut self.rng, rows.total(), model.p, |rng, i| {
+            g.list.insert(rows.edge(rng, i), 1);
+        });
+        if let Some(q) = model.loop_p {
+            skip(&mut self.rng, model.n as u128, q, |_, i| {
+                let v = Vertex{id: i as isize + 1};
+                g.list.insert(Edge{src: v, dst: v}, 1);
+            });
+        }
+        g
+    }
+}
+
+/// Locates the candidate edges from their index, as they are enumerated row
+/// after row: each vertex `v` has a row listing the candidate edges `u -> v`
+/// with `u < v` (or `u <= v` with loops) in the undirected graphs and the
+/// oriented digraphs, and the candidate arcs `v -> u` in the other digraphs.
+pub(crate) struct Rows {
+    /// The number of vertices
+    n: usize,
+    /// Are the rows those of the pairs of vertices (rather than of the arcs)
+    pairs: bool,
+    /// Are the self loops candidate edges
+    loops: bool,
+    /// Is each pair of vertices oriented at random
+    orient: bool,
+    /// The row of the last located edge (zero based)
+    row: usize,
+    /// The index of the first edge of that row
+    start: u128
+}
+impl Rows {
+    pub(crate) fn new(model: ErModel) -> Self {
+        let pairs = !model.has_antiparallel_arcs();
+        Rows{n: model.n, pairs, loops: model.picks_loops(), orient: pairs && model.digraph, row: 0, start: 0}
+    }
+    /// Returns the number of candidate edges of all the rows
+    pub(crate) fn total(&self) -> u128 {
+        let n = self.n as u128;
+        if self.pairs {
+            n * n.saturating_sub(1) / 2 + if self.loops { n } else { 0 }
+        } else {
+            n * (n - 1 + self.loops as u128)
+        }
+    }
+    /// Moves to the row holding the edge of the given index, so that the
+    /// edges can be located from there on
+    #[cfg(feature = "rayon")]
+    pub(crate) fn seek(&mut self, i: u128) {
+        let (pairs, loops, len) = (self.pairs, self.loops as u128, self.len(0));
+        let start = |r: u128| if pairs { (r * r - r + 2 * loops * r) / 2 } else { r * len };
+        // the row is estimated first, then corrected for the rounding errors
+        let mut r = if pairs { (2.0 * i as f64).sqrt() as u128 } else { i / len.max(1) };
+        r = r.min(self.n.saturating_sub(1) as u128);
+        while r > 0 && start(r) > i {
+            r -= 1;
+        }
+        while r + 1 < self.n as u128 && start(r + 1) <= i {
+            r += 1;
+        }
+        self.row   = r as usize;
+        self.start = start(r);
+    }
+    /// Returns the length of the given row
+    fn len(&self, row: usize) -> u128 {
+        let len = if self.pairs { row } else { self.n - 1 };
+        (len + self.loops as usize) as u128
+    }
+    /// Returns the edge of the given index, which must not be lower than
+    /// that of the last located edge
+    pub(crate) fn edge<R: Rng>(&mut self, rng: &mut R, i: u128) -> Edge {
+        while i >= self.start + self.len(self.row) {
+            self.start += self.len(self.row);
+            self.row   += 1;
+        }
+        let col        = (i - self.start) as usize;
+        let (src, dst) = if self.pairs {
+            (col, self.row)
+        } else if self.loops || col < self.row {
+            (self.row, col)
+        } else {
+            (self.row, col + 1)
+        };
+        let edge = Edge{src: Vertex{id: src as isize + 1}, dst: Vertex{id: dst as isize + 1}};
+        if self.orient && rng.gen() { edge.rev() } else { edge }
+    }
+}
+
+/// Calls `f` with the index of each success among `trials` independent
+/// Bernoulli trials of likelihood p (in increasing order). Rather than
+/// simulating each trial, it jumps from a success to the next one: the
+/// number of failures in between follows a geometric distribution. This
+/// takes a time proportional to the number of successes.
+pub(crate) fn skip<R: Rng, F: FnMut(&mut R, u128)>(rng: &mut R, trials: u128, p: f64, f: F) {
+    skip_range(rng, 0, trials, p, f)
+}
+/// Same as `skip` for the trials whose index lies in `from..to`
+pub(crate) fn skip_range<R: Rng, F: FnMut(&mut R, u128)>(rng: &mut R, from: u128, to: u128, p: f64, mut f: F) {
+    if p <= 0.0 {
+        return;
+    }
+    if p >= 1.0 {
+        for i in from..to {
+            f(rng, i);
+        }
+        return;
+    }
+    let log_q    = (1.0 - p).ln();
+    let mut next = from;
+    loop {
+        // 1 - gen() lies in (0, 1], hence its log is finite
+        let u = 1.0 - rng.gen::<f64>();
+        next  = next.saturating_add((u.ln() / log_q).floor() as u128);
+        if next >= to {
+            return;
+        }
+        f(rng, next);
+        next += 1;
+    }
+}
+
+/// Draws the number of successes among `trials` independent Bernoulli
+/// trials of likelihood p (see `skip`)
+fn binomial<R: Rng>(rng: &mut R, trials: u128, p: f64) -> usize {
+    let mut count = 0;
+    skip(rng, trials, p, |_, _| count += 1);
+    count
+}
@@ -0,0 +1,94 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module defines the certificates of the structures planted in the
+//! graphs when they are generated. A planted structure bounds the optimum of
+//! some problem on the graph, which comes in handy to validate solvers.
+
+use crate::{Graph, Vertex, Weight};
+use std::io::{self, Write};
+
+/// A structure planted in a graph when it was generated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Certificate {
+    /// A set of vertices no edge joins: the maximum independent set of the
+    /// graph has at least as many vertices
+    IndependentSet(Vec<Vertex>),
+    /// A set of vertices each edge touches: the minimum vertex cover of the
+    /// graph has at most as many vertices
+    VertexCover(Vec<Vertex>),
+    /// One side of a bisection of the vertices: the cut between both sides
+    /// is a maximum cut when the edges it cuts weigh positive and the others
+    /// negative
+    Cut(Vec<Vertex>)
+}
+impl Certificate {
+    /// Returns the same certificate where the vertex `v` became the vertex
+    /// `moved(v)`
+    pub(crate) fn renumbered(&self, moved: impl Fn(Vertex) -> Vertex) -> Self {
+        let renumbered = |vertices: &[Vertex]| {
+            let mut vertices = vertices.iter().map(|v| moved(*v)).collect::<Vec<Vertex>>();
+            vertices.sort_unstable();
+            vertices
+        };
+        match self {
+            Certificate::IndependentSet(vertices) => Certificate::IndependentSet(renumbered(vertices)),
+            Certificate::VertexCover(vertices)    => Certificate::VertexCover(renumbered(vertices)),
+            Certificate::Cut(side)                => Certificate::Cut(renumbered(side))
+        }
+    }
+}
+
+impl<W> Graph<W> {
+    /// Returns the certificate of the structure planted in the graph when
+    /// it was generated (if any). It is forgotten by the transformations
+    /// which could invalidate it.
+    pub fn certificate(&self) -> Option<&Certificate> {
+        self.certificate.as_ref()
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Writes the certificate of the planted structure (if any) as comment
+    /// lines starting with the given prefix, where the vertices are numbered
+    /// from one
+    pub(crate) fn write_certificate<O: Write>(&self, out: &mut O, prefix: &str) -> io::Result<()> {
+        let (name, vertices) = match self.certificate.as_ref() {
+            None => return Ok(()),
+            Some(Certificate::IndependentSet(vertices)) => ("independent set", vertices),
+            Some(Certificate::VertexCover(vertices))    => ("vertex cover", vertices),
+            Some(Certificate::Cut(side))                => ("cut side", side)
+        };
+        match self.certificate {
+            Some(Certificate::IndependentSet(_)) => writeln!(out, "{} An independent set of {} vertices was planted (by construction)", prefix, vertices.len())?,
+            Some(Certificate::VertexCover(_))    => writeln!(out, "{} A vertex cover of {} vertices was planted (by construction)", prefix, vertices.len())?,
+            _ => writeln!(out, "{} A cut was planted (by construction): one of its sides has {} vertices", prefix, vertices.len())?
+        }
+        write!(out, "{} Planted {}:", prefix, name)?;
+        for v in vertices.iter() {
+            write!(out, " {}", v.id)?;
+        }
+        writeln!(out)?;
+        if let Some(value) = self.planted_cut_value() {
+            writeln!(out, "{} The planted cut has value {} (it is maximum when the cut edges weigh positive and the others negative)", prefix, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module generates minimum clique cover (and clique partition)
+//! instances. The graphs are built as the union of overlapping random
+//! cliques, so that the size of the optimal cover is bounded by construction.
+
+use crate::{Edge, Graph, GraphGenError, GraphMeta, Vertex};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::seq::SliceRandom;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+/// A graph built as the union of `c` random cliques which, together, cover
+/// all of its vertices. Any subset of a clique is a clique; hence both the
+/// minimum clique cover and the minimum clique partition are at most `c`.
+#[derive(Debug, Clone)]
+pub struct CliqueCoverInstance {
+    g      : Graph,
+    cliques: Vec<Vec<isize>>
+}
+impl CliqueCoverInstance {
+    /// Creates a graph with `n` vertices made of `c` cliques. Each vertex is
+    /// first assigned to one clique (so that all cliques are non empty when
+    /// c <= n) and then joins each of the other cliques with probability p.
+    /// It panics when the parameters are invalid (see `try_new`).
+    pub fn new(n: usize, c: usize, p: f64) -> Self {
+        Self::try_new(n, c, p).expect("invalid clique cover parameters")
+    }
+    /// Same as `new` but fails when there is no vertex, when p is not a
+    /// probability or when there is no clique
+    pub fn try_new(n: usize, c: usize, p: f64) -> Result<Self, GraphGenError> {
+        GraphGenError::check(n, p)?;
+        if c == 0 {
+            return Err(GraphGenError::Conflict("a clique cover needs at least one clique"));
+        }
+        let mut rng = crate::random::rng();
+        let coin    = Bernoulli::new(p).expect("p is a probability");
+
+        let mut order = (1..=n as isize).collect::<Vec<isize>>();
+        order.shuffle(&mut rng);
+
+        let mut members = vec![BTreeSet::new(); c];
+        for (i, v) in order.iter().enumerate() {
+            members[i % c].insert(*v);
+        }
+        for clique in members.iter_mut() {
+            for v in 1..=n as isize {
+                if coin.sample(&mut rng) {
+                    clique.insert(v);
+                }
+            }
+        }
+
+        let nb_cliques = members.iter().filter(|m| !m.is_empty()).count();
+        let mut g      = Graph::empty(GraphMeta::new(format!("union of {} overlapping cliques (overlap probability {}){}", nb_cliques, p, crate::random::seed_suffix(crate::random::thread_seed()))), n);
+        for clique in members.iter() {
+            let vertices = clique.iter().copied().collect::<Vec<isize>>();
+            for (i, u) in vertices.iter().enumerate() {
+                for v in vertices[i+1..].iter() {
+                    g.list.insert(Edge{src: Vertex{id: *u}, dst: Vertex{id: *v}}, 1);
+                }
+            }
+        }
+
+        let cliques = members.into_iter()
+            .filter(|m| !m.is_empty())
+            .map(|m| m.into_iter().collect())
+            .collect();
+
+        Ok(CliqueCoverInstance{g, cliques})
+    }
+
+    /// Returns the graph of the instance
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+    /// Returns the cliques covering the graph (by construction)
+    pub fn cliques(&self) -> &[Vec<isize>] {
+        &self.cliques
+    }
+
+    /// Writes the instance in DIMACS format. The planted cliques are listed
+    /// in comments (`c clique ...`) at the end of the file.
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "c Pseudo-random {}", self.g.meta.provenance)?;
+        writeln!(out, "c The minimum clique cover of this graph has at most {} cliques (by construction)", self.cliques.len())?;
+        writeln!(out, "c This graph has {} vertices and {} edges", self.g.n, self.g.list.len())?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p edge {} {}", self.g.n, self.g.list.len())?;
+
+        for edge in self.g.list.keys() {
+            writeln!(out, "e {} {}", edge.src.id, edge.dst.id)?;
+        }
+
+        writeln!(out, "c Planted cliques")?;
+        for clique in self.cliques.iter() {
+            let vertices = clique.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+            writeln!(out, "c clique {}", vertices.join(" "))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module provides the SAT encoding of the graph coloring problem. It
+//! lets one use the very same random graphs to produce k-colorability
+//! benchmarks whose hardness is controlled by the G(n, p) parameters.
+//!
+//! It also provides a family of coloring benchmarks whose chromatic number
+//! is known by construction.
+
+use crate::{Edge, Graph, GraphGenError, GraphMeta, Vertex};
+use rand::distributions::{Bernoulli, Distribution, Uniform};
+use rand::seq::SliceRandom;
+use std::io::{self, Write};
+
+/// A k-colorability instance derived from a random graph. It is encoded in
+/// CNF using the *direct* encoding: variable `(v-1) * k + c` is true iff the
+/// vertex `v` is assigned the color `c`.
+#[derive(Debug, Clone)]
+pub struct ColoringSatGraph {
+    g: Graph,
+    k: usize
+}
+impl ColoringSatGraph {
+    pub fn new(g: Graph, k: usize) -> Self {
+        ColoringSatGraph{g, k}
+    }
+
+    /// Returns the graph underlying this instance
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+
+    /// returns the number of boolean variables in the encoding
+    fn nb_variables(&self) -> usize {
+        self.g.n * self.k
+    }
+    /// returns the number of clauses in the encoding
+    fn nb_clauses(&self) -> usize {
+        let at_least_one = self.g.n;
+        let at_most_one  = self.g.n * (self.k * self.k.saturating_sub(1) / 2);
+        let conflicts    = self.g.list.len() * self.k;
+
+        at_least_one + at_most_one + conflicts
+    }
+    /// returns the variable meaning that vertex `v` takes the color `c`
+    fn variable(&self, v: isize, c: usize) -> isize {
+        (v - 1) * self.k as isize + c as isize
+    }
+
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let gtype = if self.g.meta.digraph    { "digraph" } else {"graph"};
+        let loops = if self.g.meta.self_loops { "" }        else { " NOT"};
+        writeln!(out, "c Pseudo-random {}-coloring instance on a {} from {}", self.k, gtype, self.g.meta.provenance)?;
+        writeln!(out, "c it was generated to{} allow self loops", loops)?;
+        writeln!(out, "c The graph has {} vertices and {} edges", self.g.n, self.g.list.len())?;
+        writeln!(out, "c This instance has {} variables and {} clauses", self.nb_variables(), self.nb_clauses())?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Variable (v-1)*{} + c is true iff vertex v takes color c", self.k)?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p cnf {} {}", self.nb_variables(), self.nb_clauses())?;
+
+        writeln!(out, "c Each vertex takes at least one color")?;
+        for v in 1..=self.g.n as isize {
+            let clause = (1..=self.k)
+                .map(|c| self.variable(v, c).to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+            writeln!(out, "{} 0", clause)?;
+        }
+
+        writeln!(out, "c Each vertex takes at most one color")?;
+        for v in 1..=self.g.n as isize {
+            for c in 1..=self.k {
+                for d in c+1..=self.k {
+                    writeln!(out, "-{} -{} 0", self.variable(v, c), self.variable(v, d))?;
+                }
+            }
+        }
+
+        writeln!(out, "c Adjacent vertices never share the same color")?;
+        for (edge, _w) in self.g.list.iter() {
+            for c in 1..=self.k {
+                writeln!(out, "-{} -{} 0", self.variable(edge.src.id, c), self.variable(edge.dst.id, c))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
+
+/// A coloring benchmark whose chromatic number is known by construction.
+/// The vertices are randomly partitioned into `k` color classes, and each
+/// pair of vertices from distinct classes is connected with probability p.
+/// Because the graph is k-partite, it is k-colorable. Because it embeds a
+/// k-clique (one representative per class), it needs k colors.
+#[derive(Debug, Clone)]
+pub struct PlantedColoring {
+    g      : Graph,
+    k      : usize,
+    /// The planted color of each vertex (in `1..=k`)
+    classes: Vec<usize>
+}
+impl PlantedColoring {
+    /// Creates a graph with `n` vertices and chromatic number `k`. It panics
+    /// when the parameters are invalid (see `try_new`).
+    pub fn new(n: usize, k: usize, p: f64) -> Self {
+        Self::try_new(n, k, p).expect("invalid planted coloring parameters")
+    }
+    /// Same as `new` but fails when there is no vertex, when p is not a
+    /// probability or when k is not in `1..=n`
+    pub fn try_new(n: usize, k: usize, p: f64) -> Result<Self, GraphGenError> {
+        GraphGenError::check(n, p)?;
+        if k == 0 || k > n {
+            return Err(GraphGenError::Conflict("the chromatic number must be between 1 and the number of vertices"));
+        }
+        let mut rng = crate::random::rng();
+        let coin    = Bernoulli::new(p).expect("p is a probability");
+        let color   = Uniform::new_inclusive(1, k);
+
+        // the first k vertices (in random order) ensure no class is empty
+        let mut order = (0..n).collect::<Vec<usize>>();
+        order.shuffle(&mut rng);
+        let mut classes = vec![0; n];
+        for (i, v) in order.iter().enumerate() {
+            classes[*v] = if i < k { i + 1 } else { color.sample(&mut rng) };
+        }
+        let mut clique = vec![false; n];
+        for v in order.iter().take(k) {
+            clique[*v] = true;
+        }
+
+        let mut g = Graph::empty(GraphMeta::new(format!("quasi {}-partite graph with an embedded {}-clique (p = {}){}", k, k, p, crate::random::seed_suffix(crate::random::thread_seed()))), n);
+        for u in 0..n {
+            for v in u+1..n {
+                if classes[u] == classes[v] {
+                    continue;
+                }
+                let planted = clique[u] && clique[v];
+                if planted || coin.sample(&mut rng) {
+                    let src = Vertex{id: u as isize + 1};
+                    let dst = Vertex{id: v as isize + 1};
+                    g.list.insert(Edge{src, dst}, 1);
+                }
+            }
+        }
+
+        Ok(PlantedColoring{g, k, classes})
+    }
+
+    /// Returns the graph of the benchmark
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+    /// Returns the planted coloring (the color of vertex `v` is at `v-1`)
+    pub fn coloring(&self) -> &[usize] {
+        &self.classes
+    }
+
+    /// Writes the benchmark in the DIMACS coloring format
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "c Pseudo-random {}", self.g.meta.provenance)?;
+        writeln!(out, "c The chromatic number of this graph is {} (by construction)", self.k)?;
+        writeln!(out, "c This graph has {} vertices and {} edges", self.g.n, self.g.list.len())?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p edge {} {}", self.g.n, self.g.list.len())?;
+
+        for edge in self.g.list.keys() {
+            writeln!(out, "e {} {}", edge.src.id, edge.dst.id)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+
+//! This module compares the empirical quantities of a generated graph (its
+//! edge count, degree distribution and clustering) with their expectation
+//! under the Erdos-Renyi model which produced it. This is a statistical
+//! self-test of the generator.
+
+use crate::{ErModel, Graph};
+use std::fmt;
+
+/// An empirical quantity along with its expectation under the model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    /// The name of the quantity (e.g. `edges`)
+    pub quantity: &'static str,
+    /// The value observed in the graph
+    pub observed: f64,
+    /// The value expected under the model
+    pub expected: f64,
+    /// The largest deviation from the expected value which is not deemed
+    /// suspicious (about three standard deviations)
+    pub tolerance: f64
+}
+impl Comparison {
+    /// Returns true iff the observed value deviates from the expected one by
+    /// more than the tolerance
+    pub fn deviates(&self) -> bool {
+        (self.observed - self.expected).abs() > self.tolerance
+    }
+}
+/// Writes the comparison as `quantity: observed o, expected e ± t` followed
+/// by its verdict
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: observed {:.4}, expected {:.4} ± {:.4}: {}", self.quantity,
+            self.observed, self.expected, self.tolerance, if self.deviates() { "DEVIATES" } else { "ok" })
+    }
+}
+
+impl ErModel {
+    /// Compares the edge count, the mean and variance of the degrees, and the
+    /// global clustering coefficient of the graph with their expectation
+    /// under this model (the tolerances are about three standard deviations,
+    /// hence a sound generator deviates in a small fraction of the graphs).
+    /// The expectations are those of the unconstrained model: they are only
+    /// indicative when the model has constraints (see `has_constraints`).
+    pub fn compare<W>(self, g: &Graph<W>) -> Vec<Comparison> {
+        let n        = self.n as f64;
+        let possible = self.nb_possible_edges() as f64;
+        let q        = self.loop_p.unwrap_or(0.0);
+        // the count of edges is fixed by the model unless they are included
+        // independently (up to the rounding of both counts)
+        let edges_var = if self.bernoulli { possible * self.p * (1.0 - self.p) + n * q * (1.0 - q) } else { 0.0 };
+        let edges     = Comparison {
+            quantity : "edges",
+            observed : g.list.len() as f64,
+            expected : possible * self.p + n * q,
+            tolerance: 3.0 * edges_var.sqrt() + 1.0
+        };
+        // the degree of a vertex sums its pair slots (weighing 1) and its
+        // loop slot (weighing 2), each of which is picked with its likelihood
+        let slots  = if self.has_antiparallel_arcs() { 2.0 * (n - 1.0) } else { n - 1.0 };
+        let loop_p = if self.picks_loops() { self.p } else { q };
+        let var    = slots * self.p * (1.0 - self.p) + 4.0 * loop_p * (1.0 - loop_p);
+        let stats  = g.stats();
+        let mut comparisons = vec![
+            edges,
+            Comparison {
+                quantity : "mean degree",
+                observed : stats.mean_degree,
+                expected : 2.0 * edges.expected / n,
+                tolerance: 2.0 * edges.tolerance / n
+            },
+            Comparison {
+                quantity : "degree variance",
+                observed : stats.degree_variance,
+                expected : var,
+                tolerance: 3.0 * var * (2.0 / (n - 1.0).max(1.0)).sqrt() + 1.0 / n
+            }
+        ];
+        // each wedge is closed when its endpoints are adjacent, and each
+        // triangle closes three wedges at once (hence the variance). The
+        // number of adjacent pairs fluctuates too, unless it is fixed by the
+        // number of edges (anti-parallel arcs join the same pair).
+        let pairs    = n * (n - 1.0) / 2.0;
+        let adjacent = if self.has_antiparallel_arcs() { 1.0 - (1.0 - self.p).powi(2) } else { self.p };
+        let density_var = if self.bernoulli || self.has_antiparallel_arcs() { adjacent * (1.0 - adjacent) / pairs.max(1.0) } else { 0.0 };
+        let wedges   = g.vertices()
+            .map(|v| g.neighbors(v).iter().filter(|u| **u != v).count())
+            .map(|k| (k * k.saturating_sub(1) / 2) as f64)
+            .sum::<f64>();
+        if wedges > 0.0 {
+            comparisons.push(Comparison {
+                quantity : "global clustering",
+                observed : g.global_clustering(),
+                expected : adjacent,
+                tolerance: 3.0 * (3.0 * adjacent * (1.0 - adjacent) / wedges + density_var).sqrt()
+            });
+        }
+        comparisons
+    }
+    /// Returns true iff the model puts constraints on the generated graphs
+    /// (connectivity, degree bounds, girth or planted structures), which
+    /// bias them away from the plain G(n, p) expectations
+    pub fn has_constraints(self) -> bool {
+        self.connectivity.is_some() || self.no_isolated || self.max_degree.is_some()
+            || self.min_degree.is_some() || self.girth.is_some() || self.planted.is_some()
+    }
+}
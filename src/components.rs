@@ -0,0 +1,143 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module finds the connected components of the graphs. In a digraph,
+//! these are the weakly connected components (the orientation of the arcs is
+//! ignored), unless the strongly connected components are asked for.
+
+use crate::{Graph, Vertex};
+
+impl<W> Graph<W> {
+    /// Returns the connected components of the graph. Each component lists
+    /// its vertices in increasing id order, and the components are sorted
+    /// after their smallest vertex.
+    pub fn components(&self) -> Vec<Vec<Vertex>> {
+        let mut components = vec![];
+        let mut visited    = vec![false; self.n];
+        for start in self.vertices() {
+            if visited[start.id as usize - 1] {
+                continue;
+            }
+            visited[start.id as usize - 1] = true;
+
+            let mut component = vec![];
+            let mut stack     = vec![start];
+            while let Some(v) = stack.pop() {
+                component.push(v);
+                for u in self.neighbors(v) {
+                    if !visited[u.id as usize - 1] {
+                        visited[u.id as usize - 1] = true;
+                        stack.push(*u);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+    /// Returns the sizes of the connected components of the graph (weakly
+    /// connected components for a digraph) in non-increasing order
+    pub fn connected_components(&self) -> Vec<usize> {
+        let mut sizes = self.components().iter().map(|c| c.len()).collect::<Vec<usize>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+    /// Returns the strongly connected components of the graph: the maximal
+    /// sets of vertices which can all reach each other following the arcs.
+    /// These are the connected components of an undirected graph. Each
+    /// component lists its vertices in increasing id order, and the
+    /// components are sorted after their smallest vertex. This is Tarjan's
+    /// algorithm (without recursion, so that long paths cannot overflow the
+    /// stack).
+    pub fn strongly_connected_components(&self) -> Vec<Vec<Vertex>> {
+        if !self.meta.digraph {
+            return self.components();
+        }
+        const UNVISITED: usize = usize::MAX;
+        let mut index      = vec![UNVISITED; self.n];
+        let mut low        = vec![0; self.n];
+        let mut on_stack   = vec![false; self.n];
+        let mut stack      = vec![];
+        let mut components = vec![];
+        let mut next_index = 0;
+        for start in self.vertices() {
+            if index[start.id as usize - 1] != UNVISITED {
+                continue;
+            }
+            // each frame holds a vertex and the position of the next
+            // successor to look at
+            let mut frames = vec![(start, 0)];
+            while let Some((v, i)) = frames.pop() {
+                let vi = v.id as usize - 1;
+                if i == 0 {
+                    index[vi]    = next_index;
+                    low[vi]      = next_index;
+                    next_index  += 1;
+                    stack.push(v);
+                    on_stack[vi] = true;
+                }
+                if let Some(u) = self.out_neighbors(v).get(i).copied() {
+                    frames.push((v, i + 1));
+                    let ui = u.id as usize - 1;
+                    if index[ui] == UNVISITED {
+                        frames.push((u, 0));
+                    } else if on_stack[ui] {
+                        low[vi] = low[vi].min(index[ui]);
+                    }
+                    continue;
+                }
+                if low[vi] == index[vi] {
+                    let mut component = vec![];
+                    while let Some(u) = stack.pop() {
+                        on_stack[u.id as usize - 1] = false;
+                        component.push(u);
+                        if u == v {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+                if let Some((parent, _)) = frames.last() {
+                    let pi  = parent.id as usize - 1;
+                    low[pi] = low[pi].min(low[vi]);
+                }
+            }
+        }
+        components.sort_unstable_by_key(|c| c[0]);
+        components
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    /// Returns the subgraph induced by the largest connected component of the
+    /// graph (the first one of `components` in case of a tie). Its vertices
+    /// are renumbered as in `induced_subgraph`.
+    pub fn largest_component(&self) -> Graph<W> {
+        let largest = self.components().into_iter()
+            .rev()
+            .max_by_key(|c| c.len())
+            .unwrap_or_default();
+        let mut g = self.induced_subgraph(&largest).expect("a component is made of distinct vertices of the graph");
+        g.meta    = self.derived_meta("largest connected component");
+        g
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module lets the Erdos-Renyi model guarantee the connectivity of the
+//! generated graphs (weak or strong connectivity for digraphs), which many
+//! algorithms assume of their inputs.
+
+use crate::{Edge, ErGenerator, ErModel, Vertex};
+use crate::constraints::Sample;
+use rand::Rng;
+use rand::seq::{index, SliceRandom};
+use std::str::FromStr;
+
+/// How the connectivity of the generated graphs is guaranteed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// The graphs are sampled again until one of them is connected. This
+    /// keeps the G(n, p) distribution (conditioned on the connectivity) but
+    /// can take very long when p is below the connectivity threshold
+    /// `ln(n) / n`.
+    Resample,
+    /// A random spanning tree is planted in each graph before the other
+    /// edges are picked. The graphs then have at least `n - 1` edges.
+    SpanningTree,
+    /// A random Hamiltonian cycle is planted in each graph before the other
+    /// edges are picked. The graphs then have at least `n` edges (when n > 2)
+    /// and the digraphs are strongly connected.
+    HamiltonianCycle
+}
+impl FromStr for Connectivity {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Self, Self::Err> {
+        match txt.to_lowercase().as_str() {
+            "resample" => Ok(Connectivity::Resample),
+            "tree"     => Ok(Connectivity::SpanningTree),
+            "cycle"    => Ok(Connectivity::HamiltonianCycle),
+            _          => Err(txt.to_owned())
+        }
+    }
+}
+
+impl ErModel {
+    /// Only generates connected graphs (weakly connected digraphs), see
+    /// `Connectivity`
+    pub fn connected(self, connectivity: Connectivity) -> Self {
+        ErModel{connectivity: Some(connectivity), ..self}
+    }
+}
+
+impl<R: Rng> ErGenerator<R> {
+    /// The number of random parents tried before looking at all of them when
+    /// planting a spanning tree
+    const PARENT_TRIES: usize = 64;
+
+    /// Plants a random spanning tree in the graph: the vertices are visited
+    /// in a random order and each of them is attached to one of the vertices
+    /// visited before it (which has not reached the maximum degree yet, and
+    /// which the planted structures allow to join it). The arcs of a digraph
+    /// point away from the first vertex. A vertex is left out of the tree in
+    /// the rare cases where no vertex can be its parent.
+    pub(crate) fn plant_spanning_tree(&mut self, s: &mut Sample) {
+        let max_degree = self.model.max_degree.unwrap_or(usize::MAX);
+        let mut order  = s.graph.vertices().collect::<Vec<Vertex>>();
+        order.shuffle(&mut self.rng);
+        // the tree is rooted outside of the independent set, and its first
+        // two vertices lie on both sides of a bipartite cut
+        if let Some(root) = order.iter().position(|v| !s.is_independent(*v)) {
+            order.swap(0, root);
+        }
+        if s.is_bipartite() && order.len() > 1 {
+            let root = order[0];
+            if let Some(other) = order.iter().position(|v| s.side(*v) != s.side(root)) {
+                order.swap(1, other);
+            }
+        }
+        for i in 1..order.len() {
+            let child = order[i];
+            let fits  = |s: &Sample, parent: Vertex| {
+                s.degree(parent) < max_degree && s.may_join(parent, child)
+            };
+            let mut parent = None;
+            for _ in 0..Self::PARENT_TRIES {
+                let candidate = order[self.rng.gen_range(0, i)];
+                if fits(s, candidate) {
+                    parent = Some(candidate);
+                    break;
+                }
+            }
+            if parent.is_none() {
+                let candidates = order[..i].iter().copied()
+                    .filter(|v| fits(s, *v))
+                    .collect::<Vec<Vertex>>();
+                parent = candidates.choose(&mut self.rng).copied();
+            }
+            if let Some(parent) = parent {
+                s.insert(Edge{src: parent, dst: child});
+            }
+        }
+    }
+    /// Plants a random Hamiltonian cycle in the graph: the vertices are
+    /// visited in a random order and each of them is linked to the next one
+    /// (the last one being linked to the first one). No two vertices of the
+    /// planted independent set (or of a same side of a bipartite cut) follow
+    /// each other in that order.
+    pub(crate) fn plant_hamiltonian_cycle(&mut self, s: &mut Sample) {
+        let (mut independent, mut others): (Vec<Vertex>, Vec<Vertex>) = s.graph.vertices()
+            .partition(|v| s.is_independent(*v) || (s.is_bipartite() && s.side(*v)));
+        if independent.len() + others.len() < 2 {
+            return;
+        }
+        independent.shuffle(&mut self.rng);
+        others.shuffle(&mut self.rng);
+        // each independent vertex goes after a distinct other vertex
+        let mut after = vec![None; others.len()];
+        for (v, i) in independent.iter().zip(index::sample(&mut self.rng, others.len(), independent.len()).into_iter()) {
+            after[i] = Some(*v);
+        }
+        let order = others.iter().zip(after.iter())
+            .flat_map(|(v, a)| std::iter::once(*v).chain(*a))
+            .collect::<Vec<Vertex>>();
+        for i in 0..order.len() {
+            let edge = Edge{src: order[i], dst: order[(i + 1) % order.len()]};
+            if !s.contains(edge) {
+                s.insert(edge);
+            }
+        }
+    }
+}
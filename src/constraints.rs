@@ -0,0 +1,278 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module implements the constraints the Erdos-Renyi model can put on
+//! the degrees of the vertices of the generated graphs, and on the cycles
+//! they contain.
+
+use crate::{Edge, ErGenerator, ErModel, Graph, Vertex};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+
+/// The graph being sampled along with the degrees and neighbours of its
+/// vertices (which cannot be asked to the graph itself as its adjacency index
+/// would be built before the graph is complete)
+pub(crate) struct Sample {
+    /// The graph being sampled
+    pub(crate) graph: Graph,
+    /// The degree of each vertex (a self loop counts twice)
+    degrees: Vec<usize>,
+    /// The neighbours of each vertex (both the successors and predecessors
+    /// in a digraph)
+    neighbors: Vec<Vec<Vertex>>,
+    /// Are the edges `u -> v` and `v -> u` the same (or exclusive)
+    symmetric: bool,
+    /// Does each vertex belong to the planted independent set
+    independent: Vec<bool>,
+    /// Is each vertex on the first side of the planted cut
+    side: Vec<bool>,
+    /// Are the edges between the vertices of a same side forbidden
+    bipartite: bool
+}
+impl Sample {
+    /// Creates an empty sample with room for the given number of edges
+    pub(crate) fn new(model: ErModel, nb_edges: usize) -> Self {
+        let mut graph = Graph::empty(model.into(), model.n);
+        graph.list.reserve(nb_edges);
+        Sample{graph, degrees: vec![0; model.n], neighbors: vec![vec![]; model.n], symmetric: !model.has_antiparallel_arcs(), independent: vec![false; model.n], side: vec![false; model.n], bipartite: false}
+    }
+    /// Returns the number of edges sampled so far
+    pub(crate) fn len(&self) -> usize {
+        self.graph.list.len()
+    }
+    /// Returns the degree of `v`
+    pub(crate) fn degree(&self, v: Vertex) -> usize {
+        self.degrees[v.id as usize - 1]
+    }
+    /// Returns true iff the edge would close a cycle shorter than the given
+    /// girth: its endpoints are at most `girth - 2` edges apart (this is a
+    /// breadth first search bounded to that depth). A self loop is a cycle
+    /// of length 1, while an arc anti-parallel to an existing one closes no
+    /// new cycle in the underlying undirected graph.
+    pub(crate) fn closes_short_cycle(&self, edge: Edge, girth: usize) -> bool {
+        if edge.is_self_loop() {
+            return girth > 1;
+        }
+        if self.joins(edge) {
+            return false;
+        }
+        let (u, v) = if self.degree(edge.src) < self.degree(edge.dst) { (edge.src, edge.dst) } else { (edge.dst, edge.src) };
+        let mut visited  = HashSet::new();
+        let mut frontier = vec![u];
+        visited.insert(u);
+        for _ in 0..girth.saturating_sub(2) {
+            let mut next = vec![];
+            for w in frontier {
+                for x in self.neighbors[w.id as usize - 1].iter() {
+                    if *x == v {
+                        return true;
+                    }
+                    if visited.insert(*x) {
+                        next.push(*x);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        false
+    }
+    /// Puts `v` in the planted independent set
+    pub(crate) fn set_independent(&mut self, v: Vertex) {
+        self.independent[v.id as usize - 1] = true;
+    }
+    /// Returns true iff `v` belongs to the planted independent set
+    pub(crate) fn is_independent(&self, v: Vertex) -> bool {
+        self.independent[v.id as usize - 1]
+    }
+    /// Puts `v` on the first side of the planted cut
+    pub(crate) fn set_side(&mut self, v: Vertex) {
+        self.side[v.id as usize - 1] = true;
+    }
+    /// Returns true iff `v` is on the first side of the planted cut
+    pub(crate) fn side(&self, v: Vertex) -> bool {
+        self.side[v.id as usize - 1]
+    }
+    /// Forbids the edges between the vertices of a same side of the cut
+    pub(crate) fn set_bipartite(&mut self) {
+        self.bipartite = true;
+    }
+    /// Returns true iff the edges between the vertices of a same side of
+    /// the cut are forbidden
+    pub(crate) fn is_bipartite(&self) -> bool {
+        self.bipartite
+    }
+    /// Returns true iff the planted structures allow an edge between `u`
+    /// and `v`
+    pub(crate) fn may_join(&self, u: Vertex, v: Vertex) -> bool {
+        let independent = self.is_independent(u) && self.is_independent(v);
+        let same_side   = self.bipartite && self.side(u) == self.side(v);
+        !independent && !same_side
+    }
+    /// Returns true iff the graph has the edge (in either orientation,
+    /// unless anti-parallel arcs are allowed)
+    pub(crate) fn contains(&self, edge: Edge) -> bool {
+        self.joins(edge) && (self.symmetric || self.graph.list.contains_key(&edge))
+    }
+    /// Returns true iff the graph has an edge between the endpoints of the
+    /// given edge (in either orientation)
+    pub(crate) fn joins(&self, edge: Edge) -> bool {
+        self.graph.list.contains_key(&edge) || self.graph.list.contains_key(&edge.rev())
+    }
+    /// Adds the edge to the graph
+    pub(crate) fn insert(&mut self, edge: Edge) {
+        self.graph.list.insert(edge, 1);
+        self.degrees[edge.src.id as usize - 1] += 1;
+        self.degrees[edge.dst.id as usize - 1] += 1;
+        self.neighbors[edge.src.id as usize - 1].push(edge.dst);
+        if !edge.is_self_loop() {
+            self.neighbors[edge.dst.id as usize - 1].push(edge.src);
+        }
+    }
+}
+
+impl ErModel {
+    /// Only generates graphs where each vertex has at least one incident
+    /// edge: an edge towards a random vertex is attached to each vertex left
+    /// isolated once the edges have been picked
+    pub fn without_isolated_vertices(self) -> Self {
+        ErModel{no_isolated: true, ..self}
+    }
+    /// Only generates graphs where each vertex has at most `d` incident edges
+    /// (in and out arcs for digraphs): the edges which would exceed it are
+    /// rejected. When no edge can be added anymore, the graphs have fewer
+    /// edges than the model asks for.
+    pub fn max_degree(self, d: usize) -> Self {
+        ErModel{max_degree: Some(d), ..self}
+    }
+    /// Only generates graphs where each vertex has at least `d` incident
+    /// edges (in and out arcs for digraphs): each vertex is topped up with
+    /// edges towards random vertices until it reaches `d` before the other
+    /// edges are picked. The graphs can then have more edges than the model
+    /// asks for.
+    pub fn min_degree(self, d: usize) -> Self {
+        ErModel{min_degree: Some(d), ..self}
+    }
+    /// Only generates triangle-free graphs (whose underlying undirected graph
+    /// is triangle-free for digraphs): this is the same as `girth(4)`
+    pub fn triangle_free(self) -> Self {
+        self.girth(4)
+    }
+    /// Only generates graphs without any cycle shorter than `g` (in their
+    /// underlying undirected graph for digraphs): the edges which would close
+    /// such a cycle are rejected. When no edge can be added anymore, the
+    /// graphs have fewer edges than the model asks for. This is expensive as
+    /// each candidate edge costs a breadth first search of depth `g - 2`.
+    pub fn girth(self, g: usize) -> Self {
+        let girth = self.girth.map_or(g, |girth| girth.max(g));
+        ErModel{girth: Some(girth), ..self}
+    }
+}
+
+impl<R: Rng> ErGenerator<R> {
+    /// The number of random edges tried before looking at all of them when
+    /// picking a new edge incident to a vertex
+    const NEIGHBOR_TRIES: usize = 64;
+
+    /// Attaches an edge towards a random vertex to each isolated vertex of
+    /// the graph being sampled (a self loop when the graph has one vertex).
+    /// A vertex stays isolated when no edge can be attached to it without
+    /// breaking the other constraints (e.g. when all the other vertices have
+    /// reached the maximum degree).
+    pub(crate) fn attach_isolated_vertices(&mut self, s: &mut Sample) {
+        for v in s.graph.vertices() {
+            if s.degree(v) > 0 {
+                continue;
+            }
+            if self.model.n == 1 {
+                let edge = Edge{src: v, dst: v};
+                if self.accepts(s, edge) {
+                    s.insert(edge);
+                }
+            } else {
+                self.top_up(s, v, 1);
+            }
+        }
+    }
+    /// Tops up each vertex (in a random order) with edges towards random
+    /// vertices until it reaches the minimum degree. A vertex stays below it
+    /// when no edge can be attached to it without breaking the other
+    /// constraints.
+    pub(crate) fn top_up_degrees(&mut self, s: &mut Sample, min_degree: usize) {
+        let mut order = s.graph.vertices().collect::<Vec<Vertex>>();
+        order.shuffle(&mut self.rng);
+        for v in order {
+            self.top_up(s, v, min_degree);
+        }
+    }
+    /// Attaches edges towards random vertices to `v` until it reaches the
+    /// given degree (or until no edge can be attached to it anymore). The
+    /// orientation of the arcs of a digraph is random too.
+    fn top_up(&mut self, s: &mut Sample, v: Vertex, degree: usize) {
+        while s.degree(v) < degree {
+            match self.pick_incident_edge(s, v) {
+                None       => return,
+                Some(edge) => s.insert(edge)
+            }
+        }
+    }
+    /// Picks a random edge between `v` and another vertex which can be added
+    /// to the graph (if there is one)
+    fn pick_incident_edge(&mut self, s: &Sample, v: Vertex) -> Option<Edge> {
+        let n = self.model.n as isize;
+        if n < 2 {
+            return None;
+        }
+        for _ in 0..Self::NEIGHBOR_TRIES {
+            let u = self.rng.gen_range(1, n);
+            let u = Vertex{id: if u < v.id { u } else { u + 1 }};
+            let edge = if self.model.digraph && self.rng.gen() { Edge{src: u, dst: v} } else { Edge{src: v, dst: u} };
+            if self.accepts(s, edge) {
+                return Some(edge);
+            }
+        }
+        let mut candidates = vec![];
+        for u in s.graph.vertices().filter(|u| *u != v) {
+            candidates.push(Edge{src: v, dst: u});
+            if self.model.digraph {
+                candidates.push(Edge{src: u, dst: v});
+            }
+        }
+        candidates.retain(|edge| self.accepts(s, *edge));
+        candidates.choose(&mut self.rng).copied()
+    }
+    /// Returns true iff some edge can still be added to the graph being
+    /// sampled
+    pub(crate) fn can_grow(&self, s: &Sample) -> bool {
+        let n = self.model.n as isize;
+        for src in 1..=n {
+            let first = if self.model.digraph { 1 } else { src };
+            for dst in first..=n {
+                if self.accepts(s, Edge{src: Vertex{id: src}, dst: Vertex{id: dst}}) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
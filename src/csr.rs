@@ -0,0 +1,86 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in a compact binary CSR (compressed sparse row)
+//! representation. This is much smaller and faster to parse back than the
+//! textual formats when the graphs get very large.
+
+use crate::Graph;
+use std::io::{self, Write};
+
+/// The magic bytes opening every binary CSR file
+pub const CSR_MAGIC: &[u8; 8] = b"GGCSR\0\0\x01";
+
+impl Graph {
+    /// Writes the graph in binary CSR form. All integers are little endian:
+    ///
+    /// * the 8 magic bytes `GGCSR\0\0\x01`;
+    /// * flags (u64): bit 0 is set iff the graph is directed;
+    /// * the number of vertices `n` (u64);
+    /// * the number of adjacency entries `m` (u64);
+    /// * the offsets (n+1 u64): the neighbours of `v` are the entries at
+    ///   positions `offsets[v]..offsets[v+1]`;
+    /// * the targets (m u32);
+    /// * the weights (m i64).
+    ///
+    /// The vertices are numbered from zero. In an undirected graph, each edge
+    /// appears in the adjacency of both its ends (so m is twice the number
+    /// of edges, minus the self loops).
+    pub fn to_csr(&self) -> Vec<u8> {
+        let mut out = vec![];
+        self.write_csr(&mut out).expect("writing to memory cannot fail");
+        out
+    }
+
+    /// Same as `to_csr` but streams the output to the given writer
+    pub fn write_csr<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let mut adj = vec![vec![]; self.n];
+        for (e, w) in self.list.iter() {
+            adj[e.src.id as usize - 1].push((e.dst.id as u32 - 1, *w as i64));
+            if !self.meta.digraph && !e.is_self_loop() {
+                adj[e.dst.id as usize - 1].push((e.src.id as u32 - 1, *w as i64));
+            }
+        }
+        for neighbours in adj.iter_mut() {
+            neighbours.sort_unstable();
+        }
+        let m = adj.iter().map(|a| a.len()).sum::<usize>();
+
+        out.write_all(CSR_MAGIC)?;
+        out.write_all(&(self.meta.digraph as u64).to_le_bytes())?;
+        out.write_all(&(self.n as u64).to_le_bytes())?;
+        out.write_all(&(m as u64).to_le_bytes())?;
+
+        let mut offset = 0_u64;
+        out.write_all(&offset.to_le_bytes())?;
+        for neighbours in adj.iter() {
+            offset += neighbours.len() as u64;
+            out.write_all(&offset.to_le_bytes())?;
+        }
+        for (v, _) in adj.iter().flatten() {
+            out.write_all(&v.to_le_bytes())?;
+        }
+        for (_, w) in adj.iter().flatten() {
+            out.write_all(&w.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs as plain CSV edge lists, which is what
+//! spreadsheets and pandas pipelines expect.
+
+use crate::Graph;
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Writes one `src,dst,weight` line per edge (followed by the values of
+    /// the additional edge attributes, if any), optionally preceded by a
+    /// header line naming the columns.
+    pub fn to_csv(&self, header: bool) -> String {
+        crate::written(|out| self.write_csv(out, header))
+    }
+
+    /// Same as `to_csv` but streams the output to the given writer
+    pub fn write_csv<W: Write>(&self, mut out: W, header: bool) -> io::Result<()> {
+        if header {
+            write!(out, "src,dst,weight")?;
+            for name in self.attrs.keys() {
+                write!(out, ",{}", name)?;
+            }
+            writeln!(out)?;
+        }
+        for (edge, w) in self.list.iter() {
+            write!(out, "{},{},{}", self.index(edge.src), self.index(edge.dst), w)?;
+            for values in self.attrs.values() {
+                match values.get(edge) {
+                    None    => write!(out, ",")?,
+                    Some(v) => write!(out, ",{}", v)?
+                }
+            }
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
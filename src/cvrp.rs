@@ -0,0 +1,104 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module generates capacitated vehicle routing (CVRP) instances: the
+//! depot and the customers are randomly scattered in the plane and each
+//! customer has a random demand.
+
+use crate::geometry::Point;
+use rand::distributions::{Distribution, Uniform};
+use std::io::{self, Write};
+
+/// A random capacitated vehicle routing instance
+#[derive(Debug, Clone)]
+pub struct CvrpInstance {
+    depot    : Point,
+    customers: Vec<Point>,
+    demands  : Vec<usize>,
+    capacity : usize,
+    /// The seed the instance was generated with (if any)
+    seed     : Option<u64>
+}
+impl CvrpInstance {
+    /// Creates an instance with `n` customers scattered (along with the
+    /// depot) in the `side x side` square. The demand of each customer is
+    /// uniformly drawn in `1..=max_demand` (but never exceeds the capacity of
+    /// the vehicles, so that the instance is always feasible).
+    pub fn new(n: usize, capacity: usize, max_demand: usize, side: usize) -> Self {
+        let mut rng = crate::random::rng();
+        let capacity= capacity.max(1);
+        let dist    = Uniform::new_inclusive(1, max_demand.max(1).min(capacity));
+
+        let mut points = Point::random_cloud(n + 1, side);
+        let depot      = points.remove(0);
+        let demands    = (0..n).map(|_| dist.sample(&mut rng)).collect();
+
+        CvrpInstance{depot, customers: points, demands, capacity, seed: crate::random::thread_seed()}
+    }
+
+    /// Writes the instance in the CVRPLIB format. The depot is node 1.
+    pub fn to_cvrplib(&self) -> String {
+        crate::written(|out| self.write_cvrplib(out))
+    }
+
+    /// Same as `to_cvrplib` but streams the output to the given writer
+    pub fn write_cvrplib<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let total = self.demands.iter().sum::<usize>();
+        writeln!(out, "NAME : cvrp{}", self.customers.len())?;
+        writeln!(out, "COMMENT : Pseudo-random CVRP with {} customers and a total demand of {}{} generated w/ graph_gen: https://github.com/xgillard/graph_gen", self.customers.len(), total, crate::random::seed_suffix(self.seed))?;
+        writeln!(out, "TYPE : CVRP")?;
+        writeln!(out, "DIMENSION : {}", self.customers.len() + 1)?;
+        writeln!(out, "EDGE_WEIGHT_TYPE : EUC_2D")?;
+        writeln!(out, "CAPACITY : {}", self.capacity)?;
+        writeln!(out, "NODE_COORD_SECTION")?;
+        writeln!(out, "1 {} {}", self.depot.x, self.depot.y)?;
+        for (i, p) in self.customers.iter().enumerate() {
+            writeln!(out, "{} {} {}", i + 2, p.x, p.y)?;
+        }
+        writeln!(out, "DEMAND_SECTION")?;
+        writeln!(out, "1 0")?;
+        for (i, d) in self.demands.iter().enumerate() {
+            writeln!(out, "{} {}", i + 2, d)?;
+        }
+        writeln!(out, "DEPOT_SECTION")?;
+        writeln!(out, "1")?;
+        writeln!(out, "-1")?;
+        writeln!(out, "EOF")?;
+
+        Ok(())
+    }
+
+    /// Writes the position of the depot and customers (use neato to render)
+    pub fn to_dot(&self) -> String {
+        crate::written(|out| self.write_dot(out))
+    }
+
+    /// Same as `to_dot` but streams the output to the given writer
+    pub fn write_dot<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "graph cvrp {{")?;
+        writeln!(out, "  1 [shape=box, pos=\"{},{}!\"];", self.depot.x, self.depot.y)?;
+        for (i, (p, d)) in self.customers.iter().zip(self.demands.iter()).enumerate() {
+            writeln!(out, "  {} [label=\"{} ({})\", pos=\"{},{}!\"];", i + 2, i + 2, d, p.x, p.y)?;
+        }
+        writeln!(out, "}}")?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module provides presets to generate instances in the exact file
+//! layouts expected by the example solvers of the ddo library
+//! (https://github.com/xgillard/ddo): misp, mcp and max2sat.
+
+use crate::{ErModel, Generatable, GraphGenError, Max2SatGraph, WeightedMaxCliqueGraph};
+
+/// The problems of the ddo examples for which instances can be generated
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DdoPreset {
+    /// Maximum (weighted) independent set: DIMACS `p edge` with `n` lines
+    /// giving the vertices weights and `e` lines listing the edges
+    Misp,
+    /// Maximum cut: `<n> <m>` header followed by one `<src> <dst> <weight>`
+    /// line per edge (comments start with `c`)
+    Mcp,
+    /// Weighted max2sat: `p wcnf <vars> <clauses>` followed by one
+    /// `<weight> <lit> <lit> 0` line per clause
+    Max2Sat
+}
+impl DdoPreset {
+    /// Returns the model to use in order to generate an instance with `n`
+    /// vertices (or variables for max2sat). All ddo examples expect simple
+    /// undirected graphs, hence neither self loops nor arcs are generated.
+    pub fn model(self, n: usize, p: f64) -> ErModel {
+        match self {
+            DdoPreset::Max2Sat => ErModel::new(2 * n, p),
+            _                  => ErModel::new(n, p)
+        }
+    }
+    /// Generates an instance of the problem with `n` vertices (or variables)
+    /// whose weights (those of the vertices for misp) are randomly picked
+    /// from the given candidates.
+    pub fn generate(self, n: usize, p: f64, weights: Option<&[isize]>) -> Generatable {
+        self.try_generate(n, p, weights).expect("invalid ddo instance parameters")
+    }
+    /// Same as `generate` but fails when the parameters are invalid
+    pub fn try_generate(self, n: usize, p: f64, weights: Option<&[isize]>) -> Result<Generatable, GraphGenError> {
+        let mut graph = self.model(n, p).try_generator()?.gen();
+
+        Ok(match self {
+            DdoPreset::Misp => {
+                let mut g = WeightedMaxCliqueGraph::new(graph);
+                if let Some(weights) = weights {
+                    g.pluck_random_weights(weights);
+                }
+                Generatable::ClqGraph {g}
+            },
+            DdoPreset::Mcp => {
+                if let Some(weights) = weights {
+                    graph.pluck_random_weights(weights);
+                }
+                Generatable::GenGraph {g: graph}
+            },
+            DdoPreset::Max2Sat => {
+                if let Some(weights) = weights {
+                    graph.pluck_random_weights(weights);
+                }
+                Generatable::GenSat {s: Max2SatGraph::new(graph)}
+            }
+        })
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+
+//! This module generates the dense graphs of the unconstrained models without
+//! checking for duplicate edges in a hash map: the candidate edges are
+//! either drawn in a packed bitset over the candidate edge space (when the
+//! number of edges is fixed), or each of them gets a Bernoulli draw of its
+//! own (in G(n, p) mode). The bitset takes a bit per candidate edge, which
+//! is a small fraction of the memory the edges of a dense graph take anyway.
+
+use crate::{Edge, ErGenerator, ErModel, Graph, Vertex};
+use crate::bernoulli::{skip, Rows};
+use rand::Rng;
+use rand::seq::index;
+
+/// A set of candidate edge indices packed in 64 bit words
+struct Bitset {
+    /// The bits of the set, the bit `i` being the bit `i % 64` of the word
+    /// `i / 64`
+    words: Vec<u64>
+}
+impl Bitset {
+    /// Creates an empty set of indices below `len`
+    fn new(len: u128) -> Self {
+        Bitset{words: vec![0; len.div_ceil(64) as usize]}
+    }
+    /// Adds `i` to the set and returns true iff it was not there yet
+    fn insert(&mut self, i: u128) -> bool {
+        let (word, bit) = ((i / 64) as usize, 1_u64 << (i % 64));
+        let fresh = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        fresh
+    }
+    /// Returns true iff `i` belongs to the set
+    fn contains(&self, i: u128) -> bool {
+        self.words[(i / 64) as usize] & (1_u64 << (i % 64)) != 0
+    }
+}
+
+impl ErModel {
+    /// returns true iff the graphs are generated densely: when the model has
+    /// no constraint and p exceeds `SPARSE_P`
+    pub(crate) fn is_dense(self) -> bool {
+        self.p > Self::SPARSE_P && !self.has_constraints()
+    }
+}
+
+impl<R: Rng> ErGenerator<R> {
+    /// Samples a dense graph without any duplicate check in a hash map. When
+    /// the number of edges is fixed, distinct random candidate edges are
+    /// drawn in a bitset until there are enough of them (the candidates
+    /// which are not edges are drawn instead when they are fewer, so that
+    /// few draws are wasted). In G(n, p) mode, each candidate edge gets a
+    /// Bernoulli draw of its own. The loops having a likelihood of their
+    /// own are drawn on their own.
+    pub(crate) fn sample_dense(&mut self) -> Graph {
+        let model    = self.model;
+        let mut rows = Rows::new(model);
+        let total    = rows.total();
+        let mut g    = Graph::empty(model.into(), model.n);
+        g.list.reserve(model.nb_edges_to_pick());
+        if model.bernoulli {
+            for i in 0..total {
+                if self.rng.gen_bool(model.p) {
+                    g.list.insert(rows.edge(&mut self.rng, i), 1);
+                }
+            }
+        } else {
+            let wanted     = (model.nb_edges_to_pick() as u128).min(total);
+            let complement = 2 * wanted > total;
+            let mut drawn  = if complement { total - wanted } else { wanted };
+            let mut bits   = Bitset::new(total);
+            while drawn > 0 {
+                if bits.insert(self.rng.gen_range(0, total)) {
+                    drawn -= 1;
+                }
+            }
+            for i in 0..total {
+                if bits.contains(i) != complement {
+                    g.list.insert(rows.edge(&mut self.rng, i), 1);
+                }
+            }
+        }
+        match model.loop_p {
+            Some(q) if model.bernoulli => skip(&mut self.rng, model.n as u128, q, |_, i| {
+                let v = Vertex{id: i as isize + 1};
+                g.list.insert(Edge{src: v, dst: v}, 1);
+            }),
+            Some(_) => for i in index::sample(&mut self.rng, model.n, model.nb_loops_to_pick()).into_iter() {
+                let v = Vertex{id: i as isize + 1};
+                g.list.insert(Edge{src: v, dst: v}, 1);
+            },
+            None => ()
+        }
+        g
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module lets the DIMACS graph writers be configured: the header can
+//! either be a `p edge <n> <m>` problem line (then the edges are written as
+//! `e <src> <dst>` lines) or a bare `<n> <m>` line (and the edges are written
+//! as `<src> <dst>` lines), the edge lines may carry the weight of the edges
+//! or not, and custom comment lines can be added to the preamble.
+
+use crate::{Graph, Weight};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// The header line of a DIMACS graph file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimacsHeader {
+    /// A bare `<n> <m>` line and `<src> <dst>` edge lines
+    Bare,
+    /// A `p edge <n> <m>` problem line and `e <src> <dst>` edge lines
+    Edge
+}
+impl FromStr for DimacsHeader {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Self, Self::Err> {
+        match txt.to_lowercase().as_str() {
+            "bare" => Ok(DimacsHeader::Bare),
+            "edge" => Ok(DimacsHeader::Edge),
+            _      => Err(txt.to_owned())
+        }
+    }
+}
+
+/// The configuration of a DIMACS graph writer. The default style is that of
+/// `Graph::to_dimacs`: a bare header and weighted edge lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DimacsStyle {
+    header  : DimacsHeader,
+    weighted: bool,
+    comments: Vec<String>
+}
+impl Default for DimacsStyle {
+    fn default() -> Self {
+        DimacsStyle{header: DimacsHeader::Bare, weighted: true, comments: vec![]}
+    }
+}
+impl DimacsStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Uses the given kind of header (and the matching edge lines)
+    pub fn header(self, header: DimacsHeader) -> Self {
+        DimacsStyle{header, ..self}
+    }
+    /// Tells whether the edge lines end with the weight of the edge
+    pub fn weighted(self, weighted: bool) -> Self {
+        DimacsStyle{weighted, ..self}
+    }
+    /// Adds a custom comment line (e.g. an experiment id or a git hash) to
+    /// the preamble of the file. The `c ` prefix is added automatically.
+    pub fn comment(mut self, comment: String) -> Self {
+        self.comments.push(comment);
+        self
+    }
+
+    /// Writes the custom comments followed by the header line
+    pub(crate) fn write_header<W: Write>(&self, out: &mut W, n: usize, m: usize) -> io::Result<()> {
+        for comment in self.comments.iter() {
+            writeln!(out, "c {}", comment)?;
+        }
+        match self.header {
+            DimacsHeader::Bare => writeln!(out, "{} {}", n, m),
+            DimacsHeader::Edge => writeln!(out, "p edge {} {}", n, m)
+        }
+    }
+    /// Writes one line per edge of the graph
+    pub(crate) fn write_edges<W: Write, T: fmt::Display>(&self, out: &mut W, g: &Graph<T>) -> io::Result<()> {
+        let prefix = match self.header {
+            DimacsHeader::Bare => "",
+            DimacsHeader::Edge => "e "
+        };
+        for (edge, w) in g.list.iter() {
+            if self.weighted {
+                writeln!(out, "{}{} {} {}", prefix, edge.src.id, edge.dst.id, w)?;
+            } else {
+                writeln!(out, "{}{} {}", prefix, edge.src.id, edge.dst.id)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Weight> Graph<T> {
+    /// Writes the graph in the DIMACS format with the given style
+    pub fn to_dimacs_styled(&self, style: &DimacsStyle) -> String {
+        crate::written(|out| self.write_dimacs_styled(out, style))
+    }
+
+    /// Same as `to_dimacs_styled` but streams the output to the given writer
+    pub fn write_dimacs_styled<W: Write>(&self, mut out: W, style: &DimacsStyle) -> io::Result<()> {
+        let gtype = if self.meta.digraph    { "digraph" } else {"graph"};
+        let loops = if self.meta.self_loops { "" }        else { " NOT"};
+        writeln!(out, "c A {} from {}", gtype, self.meta.provenance)?;
+        writeln!(out, "c it was generated to{} allow self loops", loops)?;
+        writeln!(out, "c This graph has {} vertices and {} edges", self.n, self.list.len())?;
+        self.write_certificate(&mut out, "c")?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+
+        style.write_header(&mut out, self.n, self.list.len())?;
+        style.write_edges(&mut out, self)
+    }
+}
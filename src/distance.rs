@@ -0,0 +1,96 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+
+//! This module computes the distances in the graphs: the eccentricity of
+//! their vertices and their diameter. The distances are the numbers of edges
+//! of the shortest paths in the underlying undirected graph (the orientation
+//! of the arcs of a digraph is ignored).
+
+use crate::{Graph, Vertex};
+use std::collections::VecDeque;
+
+impl<W> Graph<W> {
+    /// The number of vertices above which `diameter` is approximated (an
+    /// exact computation needs a breadth first search from each vertex)
+    pub const EXACT_DIAMETER_LIMIT: usize = 4096;
+
+    /// Returns the eccentricity of `v`: its distance to the vertex farthest
+    /// from it, or None when some vertex cannot be reached from `v`
+    pub fn eccentricity(&self, v: Vertex) -> Option<usize> {
+        let (_, ecc, reached) = self.farthest(v);
+        if reached == self.n { Some(ecc) } else { None }
+    }
+    /// Returns the diameter of the graph (the largest eccentricity of its
+    /// vertices), or None when it is not connected. It is exact when the
+    /// graph has at most `EXACT_DIAMETER_LIMIT` vertices, and approximated
+    /// with a double sweep otherwise (see `approximate_diameter`).
+    pub fn diameter(&self) -> Option<usize> {
+        if self.n <= Self::EXACT_DIAMETER_LIMIT {
+            self.exact_diameter()
+        } else {
+            self.approximate_diameter()
+        }
+    }
+    /// Returns the exact diameter of the graph (or None when it is not
+    /// connected) with a breadth first search from each vertex
+    pub fn exact_diameter(&self) -> Option<usize> {
+        let mut diameter = 0;
+        for v in self.vertices() {
+            diameter = diameter.max(self.eccentricity(v)?);
+        }
+        Some(diameter)
+    }
+    /// Returns a lower bound on the diameter of the graph (or None when it is
+    /// not connected) with a double sweep: the eccentricity of the vertex
+    /// farthest from a vertex of maximum degree. It takes two breadth first
+    /// searches, and is often exact on sparse random graphs.
+    pub fn approximate_diameter(&self) -> Option<usize> {
+        let start = self.vertices().max_by_key(|v| self.degree(*v))?;
+        let (far, _, reached) = self.farthest(start);
+        if reached < self.n {
+            return None;
+        }
+        self.eccentricity(far)
+    }
+    /// Performs a breadth first search from `v` and returns the last vertex
+    /// it reached, the distance to that vertex and the number of vertices
+    /// it reached
+    fn farthest(&self, v: Vertex) -> (Vertex, usize, usize) {
+        let mut distance = vec![usize::MAX; self.n];
+        let mut queue    = VecDeque::new();
+        let mut last     = (v, 0);
+        let mut reached  = 1;
+        distance[v.id as usize - 1] = 0;
+        queue.push_back(v);
+        while let Some(u) = queue.pop_front() {
+            let d = distance[u.id as usize - 1];
+            last  = (u, d);
+            for w in self.neighbors(u) {
+                if distance[w.id as usize - 1] == usize::MAX {
+                    distance[w.id as usize - 1] = d + 1;
+                    reached += 1;
+                    queue.push_back(*w);
+                }
+            }
+        }
+        (last.0, last.1, reached)
+    }
+}
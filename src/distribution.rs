@@ -0,0 +1,276 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module provides the distributions the edge (and vertex) weights can
+//! be drawn from, when plucking them uniformly from a fixed set of
+//! candidates is not enough.
+
+use crate::{Edge, Graph};
+use rand::Rng;
+use rand::distributions::{Distribution, WeightedIndex};
+use std::str::FromStr;
+
+/// A distribution of the edge weights. It reads `uniform:<lo>:<hi>`,
+/// `constant:<w>`, `discrete:<w1>=<p1>,<w2>=<p2>,..`,
+/// `gaussian:<mean>:<std>[:<lo>:<hi>]`, `exponential:<rate>` or
+/// `pareto:<alpha>[:<scale>]` on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeightDistribution {
+    /// Each weight is uniformly drawn from `lo..=hi`
+    Uniform{lo: isize, hi: isize},
+    /// All weights are the same
+    Constant(isize),
+    /// Each weight is one of the values, drawn with a likelihood
+    /// proportional to the associated probability
+    Discrete(Vec<(isize, f64)>),
+    /// Each weight is drawn from the normal distribution having the given
+    /// mean and standard deviation. When a range `(lo, hi)` is given, the
+    /// distribution is truncated to that range.
+    Gaussian{mean: f64, std: f64, range: Option<(f64, f64)>},
+    /// Each weight is drawn from the exponential distribution having the
+    /// given rate (its mean is `1 / rate`)
+    Exponential{rate: f64},
+    /// Each weight is drawn from the (heavy tailed) Pareto distribution
+    /// having the given exponent: it is at least `scale`, and the likelihood
+    /// of a weight greater than `x` is `(scale / x)^alpha`
+    Pareto{alpha: f64, scale: f64}
+}
+impl WeightDistribution {
+    /// Returns an error if the parameters of the distribution make no sense
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            WeightDistribution::Uniform{lo, hi} if lo > hi =>
+                Err(format!("the range {}:{} is empty", lo, hi)),
+            WeightDistribution::Discrete(values) if values.is_empty() =>
+                Err("a discrete distribution needs at least one value".to_string()),
+            WeightDistribution::Discrete(values) => {
+                if values.iter().any(|(_, p)| !p.is_finite() || *p < 0.0) || values.iter().all(|(_, p)| *p == 0.0) {
+                    Err("the probabilities of a discrete distribution must be non negative and not all zero".to_string())
+                } else {
+                    Ok(())
+                }
+            },
+            WeightDistribution::Gaussian{mean, std, range} => {
+                if !mean.is_finite() || !std.is_finite() || *std < 0.0 {
+                    Err(format!("the mean ({}) and standard deviation ({}) must be finite and the deviation positive", mean, std))
+                } else if matches!(range, Some((lo, hi)) if lo.is_nan() || hi.is_nan() || lo > hi) {
+                    Err("the truncation range of a gaussian distribution is empty".to_string())
+                } else {
+                    Ok(())
+                }
+            },
+            WeightDistribution::Exponential{rate} if !rate.is_finite() || *rate <= 0.0 =>
+                Err(format!("the rate of an exponential distribution ({}) must be positive", rate)),
+            WeightDistribution::Pareto{alpha, scale} if !alpha.is_finite() || !scale.is_finite() || *alpha <= 0.0 || *scale <= 0.0 =>
+                Err(format!("the exponent ({}) and scale ({}) of a pareto distribution must be positive", alpha, scale)),
+            _ => Ok(())
+        }
+    }
+    /// Returns a function drawing (real) weights from this distribution
+    fn sampler<R: Rng>(&self) -> Result<impl FnMut(&mut R) -> f64 + '_, String> {
+        self.validate()?;
+        let index = match self {
+            WeightDistribution::Discrete(values) =>
+                Some(WeightedIndex::new(values.iter().map(|(_, p)| *p)).map_err(|e| e.to_string())?),
+            _ => None
+        };
+        Ok(move |rng: &mut R| match self {
+            WeightDistribution::Uniform{lo, hi}  => rng.gen_range(*lo, *hi + 1) as f64,
+            WeightDistribution::Constant(w)      => *w as f64,
+            WeightDistribution::Discrete(values) => values[index.as_ref().expect("built above").sample(rng)].0 as f64,
+            WeightDistribution::Gaussian{mean, std, range} => truncated(rng, *range, |rng| mean + std * standard_normal(rng)),
+            WeightDistribution::Exponential{rate}  => -open_unit(rng).ln() / rate,
+            WeightDistribution::Pareto{alpha, scale} => scale / open_unit(rng).powf(1.0 / alpha)
+        })
+    }
+}
+
+/// The number of draws after which a truncated distribution gives up and
+/// clamps its value into the range (this only happens when the range holds
+/// a negligible part of the distribution)
+const MAX_DRAWS: usize = 1000;
+
+/// Draws values until one of them lies in the (optional) range
+fn truncated<R: Rng, F>(rng: &mut R, range: Option<(f64, f64)>, mut draw: F) -> f64
+    where F: FnMut(&mut R) -> f64
+{
+    match range {
+        None           => draw(rng),
+        Some((lo, hi)) => {
+            let mut value = draw(rng);
+            for _ in 1..MAX_DRAWS {
+                if lo <= value && value <= hi {
+                    break;
+                }
+                value = draw(rng);
+            }
+            value.max(lo).min(hi)
+        }
+    }
+}
+/// Draws a value uniformly from (0, 1]
+fn open_unit<R: Rng>(rng: &mut R) -> f64 {
+    1.0 - rng.gen::<f64>()
+}
+/// Draws a value from the standard normal distribution (Box-Muller transform)
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u = open_unit(rng);
+    let v = rng.gen::<f64>();
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+impl FromStr for WeightDistribution {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Self, Self::Err> {
+        let int  = |v: &str| v.trim().parse::<isize>().map_err(|e| format!("{}: {}", v, e));
+        let real = |v: &str| v.trim().parse::<f64>().map_err(|e| format!("{}: {}", v, e));
+        let (kind, params) = txt.split_once(':').unwrap_or((txt, ""));
+        let dist = match kind.to_lowercase().as_str() {
+            "uniform"  => {
+                let (lo, hi) = params.split_once(':')
+                    .ok_or_else(|| format!("{} is not of the form uniform:lo:hi", txt))?;
+                WeightDistribution::Uniform{lo: int(lo)?, hi: int(hi)?}
+            },
+            "constant" => WeightDistribution::Constant(int(params)?),
+            "discrete" => {
+                let values = params.split(',')
+                    .map(|pair| {
+                        let (w, p) = pair.split_once('=')
+                            .ok_or_else(|| format!("{} is not of the form weight=probability", pair))?;
+                        Ok((int(w)?, real(p)?))
+                    })
+                    .collect::<Result<Vec<(isize, f64)>, String>>()?;
+                WeightDistribution::Discrete(values)
+            },
+            "gaussian" => {
+                let params = params.split(':').map(real).collect::<Result<Vec<f64>, String>>()?;
+                match params.as_slice() {
+                    [mean, std]         => WeightDistribution::Gaussian{mean: *mean, std: *std, range: None},
+                    [mean, std, lo, hi] => WeightDistribution::Gaussian{mean: *mean, std: *std, range: Some((*lo, *hi))},
+                    _ => return Err(format!("{} is not of the form gaussian:mean:std[:lo:hi]", txt))
+                }
+            },
+            "exponential" => WeightDistribution::Exponential{rate: real(params)?},
+            "pareto"      => match params.split_once(':') {
+                None                 => WeightDistribution::Pareto{alpha: real(params)?, scale: 1.0},
+                Some((alpha, scale)) => WeightDistribution::Pareto{alpha: real(alpha)?, scale: real(scale)?}
+            },
+            _ => return Err(format!("unknown weight distribution {}", txt))
+        };
+        dist.validate()?;
+        Ok(dist)
+    }
+}
+
+impl<W> Graph<W> {
+    /// Gives each edge the weight `convert(x)` where `x` is randomly drawn
+    /// from the distribution
+    fn draw_weights<F>(&mut self, dist: &WeightDistribution, convert: F) -> Result<(), String>
+        where F: Fn(f64) -> W
+    {
+        let mut rng    = crate::random::weight_rng();
+        let mut sample = dist.sampler()?;
+
+        let mut edges  = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        for e in edges {
+            self.list.insert(e, convert(sample(&mut rng)));
+        }
+        Ok(())
+    }
+    /// Gives each edge `u -- v` the weight `convert(scale * (deg(u) + deg(v)) + x)`
+    /// where `x` is randomly drawn from the noise distribution (if any)
+    fn draw_degree_weights<F>(&mut self, scale: f64, noise: Option<&WeightDistribution>, convert: F) -> Result<(), String>
+        where F: Fn(f64) -> W
+    {
+        let mut rng    = crate::random::weight_rng();
+        let mut sample = noise.map(|dist| dist.sampler()).transpose()?;
+        let degree     = self.vertices().map(|v| self.degree(v) as f64).collect::<Vec<f64>>();
+
+        let mut edges  = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        for e in edges {
+            let x = sample.as_mut().map_or(0.0, |sample| sample(&mut rng));
+            let w = scale * (degree[e.src.id as usize - 1] + degree[e.dst.id as usize - 1]) + x;
+            self.list.insert(e, convert(w));
+        }
+        Ok(())
+    }
+}
+
+impl<W> Graph<W> {
+    /// Gives each vertex a weight randomly drawn from the distribution (and
+    /// rounded to the closest integer). An error is returned (and the graph
+    /// is left untouched) when the distribution is invalid.
+    pub fn draw_random_vertex_weights(&mut self, dist: &WeightDistribution) -> Result<(), String> {
+        let mut rng    = crate::random::weight_rng();
+        let mut sample = dist.sampler()?;
+        for v in self.vertices() {
+            self.set_vertex_weight(v, sample(&mut rng).round() as isize);
+        }
+        Ok(())
+    }
+    /// Gives each edge the named attribute, with a value randomly drawn from
+    /// the distribution (and rounded to the closest integer). An error is
+    /// returned (and the graph is left untouched) when the distribution is
+    /// invalid.
+    pub fn draw_random_attribute(&mut self, name: &str, dist: &WeightDistribution) -> Result<(), String> {
+        let mut rng    = crate::random::weight_rng();
+        let mut sample = dist.sampler()?;
+
+        let mut edges  = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        let values = edges.into_iter().map(|e| (e, sample(&mut rng).round() as isize)).collect();
+        self.attrs.insert(name.to_string(), values);
+        Ok(())
+    }
+}
+
+impl Graph {
+    /// Gives each edge a weight randomly drawn from the distribution (and
+    /// rounded to the closest integer). An error is returned (and the graph
+    /// is left untouched) when the distribution is invalid.
+    pub fn draw_random_weights(&mut self, dist: &WeightDistribution) -> Result<(), String> {
+        self.draw_weights(dist, |x| x.round() as isize)
+    }
+    /// Gives each edge `u -- v` the weight `scale * (deg(u) + deg(v))` plus a
+    /// noise randomly drawn from the given distribution (if any), rounded to
+    /// the closest integer. The heavy edges are then concentrated around the
+    /// hubs. An error is returned (and the graph is left untouched) when the
+    /// noise distribution is invalid.
+    pub fn degree_correlated_weights(&mut self, scale: f64, noise: Option<&WeightDistribution>) -> Result<(), String> {
+        self.draw_degree_weights(scale, noise, |x| x.round() as isize)
+    }
+}
+
+impl Graph<f64> {
+    /// Gives each edge a (real) weight randomly drawn from the distribution.
+    /// An error is returned (and the graph is left untouched) when the
+    /// distribution is invalid.
+    pub fn draw_random_weights(&mut self, dist: &WeightDistribution) -> Result<(), String> {
+        self.draw_weights(dist, |x| x)
+    }
+    /// Same as `Graph::degree_correlated_weights` but the weights are not
+    /// rounded
+    pub fn degree_correlated_weights(&mut self, scale: f64, noise: Option<&WeightDistribution>) -> Result<(), String> {
+        self.draw_degree_weights(scale, noise, |x| x)
+    }
+}
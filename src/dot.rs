@@ -0,0 +1,144 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the GraphViz dot language with an optional
+//! styling: the vertices can be colored after their degree or after the
+//! community they belong to, the edges can be drawn thicker as their weight
+//! increases, and the vertices can be pinned at given coordinates.
+
+use crate::{Graph, Point, Vertex, Weight};
+use std::io::{self, Write};
+
+/// How the vertices are colored in the dot output
+#[derive(Debug, Clone, PartialEq)]
+enum NodeColoring {
+    /// From light yellow (lowest degree) to dark red (highest degree)
+    Degree,
+    /// One color per community: `communities[v-1]` is the community of `v`
+    Community(Vec<usize>)
+}
+
+/// The styling configuration of the dot output. The default style produces
+/// the plain output of `Graph::to_dot`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DotStyle {
+    coloring     : Option<NodeColoring>,
+    penwidth     : bool,
+    positions    : Option<Vec<Point>>,
+    hide_isolated: bool
+}
+impl DotStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Fills the vertices with a color which gets darker as their degree
+    /// increases
+    pub fn color_by_degree(self) -> Self {
+        DotStyle{coloring: Some(NodeColoring::Degree), ..self}
+    }
+    /// Fills each vertex `v` with the color of its community
+    /// `communities[v-1]` (colors are reused beyond 12 communities)
+    pub fn color_by_community(self, communities: Vec<usize>) -> Self {
+        DotStyle{coloring: Some(NodeColoring::Community(communities)), ..self}
+    }
+    /// Draws the edges with a width proportional to their weight (from 1 for
+    /// the lightest edges up to 5 for the heaviest ones)
+    pub fn weighted_edges(self) -> Self {
+        DotStyle{penwidth: true, ..self}
+    }
+    /// Pins each vertex `v` at `positions[v-1]` (use neato to render)
+    pub fn with_positions(self, positions: Vec<Point>) -> Self {
+        DotStyle{positions: Some(positions), ..self}
+    }
+    /// Does not list the isolated vertices (they are not drawn at all) and
+    /// only lists the other vertices when they carry some attribute (as they
+    /// otherwise appear in the edges anyway). This keeps the output of large
+    /// sparse graphs short.
+    pub fn without_isolated(self) -> Self {
+        DotStyle{hide_isolated: true, ..self}
+    }
+}
+
+impl<T: Weight> Graph<T> {
+    /// Writes the graph in the dot language with the given styling
+    pub fn to_dot_styled(&self, style: &DotStyle) -> String {
+        crate::written(|out| self.write_dot_styled(out, style))
+    }
+
+    /// Same as `to_dot_styled` but streams the output to the given writer
+    pub fn write_dot_styled<W: Write>(&self, mut out: W, style: &DotStyle) -> io::Result<()> {
+        let gtype     = if self.meta.digraph { "digraph" } else {"graph"};
+        let connector = if self.meta.digraph { "->" }      else { "--" };
+
+        let mut degree = vec![0_usize; self.n];
+        for edge in self.list.keys() {
+            degree[edge.src.id as usize - 1] += 1;
+            degree[edge.dst.id as usize - 1] += 1;
+        }
+        let min_deg = degree.iter().copied().min().unwrap_or(0);
+        let max_deg = degree.iter().copied().max().unwrap_or(0);
+        let min_w   = self.list.values().map(|w| w.to_f64()).fold(f64::INFINITY, f64::min);
+        let max_w   = self.list.values().map(|w| w.to_f64()).fold(f64::NEG_INFINITY, f64::max);
+
+        writeln!(out, "{} g {{", gtype)?;
+        for v in 1..=self.n {
+            let mut attributes = vec![];
+            match &style.coloring {
+                None => {},
+                Some(NodeColoring::Degree) => {
+                    let color = 1 + (degree[v-1] - min_deg) * 8 / (max_deg - min_deg).max(1);
+                    attributes.push(format!("style=filled, colorscheme=ylorrd9, fillcolor={}", color));
+                },
+                Some(NodeColoring::Community(communities)) => {
+                    if let Some(c) = communities.get(v-1) {
+                        attributes.push(format!("style=filled, colorscheme=set312, fillcolor={}", 1 + c % 12));
+                    }
+                }
+            }
+            if let Some(label) = self.label(Vertex{id: v as isize}) {
+                attributes.push(format!("label=\"{}\"", label.replace('"', "\\\"")));
+            }
+            if let Some(class) = self.class(Vertex{id: v as isize}) {
+                attributes.push(format!("class=\"{}\"", class));
+            }
+            if let Some(p) = style.positions.as_ref().and_then(|p| p.get(v-1)) {
+                attributes.push(format!("pos=\"{},{}!\"", p.x, p.y));
+            }
+
+            if attributes.is_empty() {
+                if !style.hide_isolated {
+                    writeln!(out, "  {};", v)?;
+                }
+            } else if !style.hide_isolated || degree[v-1] > 0 {
+                writeln!(out, "  {} [{}];", v, attributes.join(", "))?;
+            }
+        }
+        for (edge, w) in self.list.iter() {
+            if style.penwidth {
+                let range = if max_w > min_w { max_w - min_w } else { 1.0 };
+                let width = 1.0 + 4.0 * (w.to_f64() - min_w) / range;
+                writeln!(out, "  {} {} {} [label={}, penwidth={:.2}];", edge.src.id, connector, edge.dst.id, w, width)?;
+            } else {
+                writeln!(out, "  {} {} {} [label={}];", edge.src.id, connector, edge.dst.id, w)?;
+            }
+        }
+        writeln!(out, "}}")
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module streams the edges of an Erdos-Renyi graph one by one, so that
+//! huge instances can be written without ever materializing the `Graph`.
+
+use crate::{Edge, ErGenerator};
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Lazily yields the edges of a random graph. With the same (seeded)
+/// generator, these are the edges of the graph `gen` would have returned,
+/// in the order they were picked. Only the set of the edges yielded so far
+/// is kept in memory (to avoid duplicates), hence the constraints of the
+/// model which need to look at the whole graph (e.g. connectivity) are not
+/// enforced, and the loops having a likelihood of their own are not yielded.
+/// The graphs which `gen` generates without picking random edges one by one
+/// (the sparse G(n, p) graphs and the dense graphs without constraints) have
+/// the same distribution as the edges yielded, but not the same edges.
+#[derive(Debug)]
+pub struct EdgeIter<'a, R: Rng> {
+    /// The generator drawing the random edges
+    generator: &'a mut ErGenerator<R>,
+    /// The edges that were already yielded
+    seen     : HashSet<Edge>,
+    /// The number of edges that remain to be yielded
+    remaining: usize
+}
+
+impl<R: Rng> ErGenerator<R> {
+    /// Returns an iterator over the edges of a new random graph (which is
+    /// never materialized)
+    pub fn edges(&mut self) -> EdgeIter<'_, R> {
+        let remaining = self.draw_nb_edges();
+        EdgeIter{generator: self, seen: HashSet::new(), remaining}
+    }
+}
+
+impl<R: Rng> Iterator for EdgeIter<'_, R> {
+    type Item = Edge;
+
+    fn next(&mut self) -> Option<Edge> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            let edge = self.generator.next_edge();
+
+            if edge.is_self_loop() && !self.generator.model.picks_loops() {
+                continue;
+            }
+            let symmetric = !self.generator.model.has_antiparallel_arcs();
+            if self.seen.contains(&edge) || (symmetric && self.seen.contains(&edge.rev())) {
+                continue;
+            }
+
+            self.seen.insert(edge);
+            self.remaining -= 1;
+            return Some(edge);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<R: Rng> ExactSizeIterator for EdgeIter<'_, R> {}
@@ -0,0 +1,74 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module builds graphs from explicit edge lists, so that the writers
+//! and transformations can also be used on graphs which were produced by
+//! some other tool.
+
+use crate::{Edge, Graph, GraphMeta, Vertex};
+
+impl Graph {
+    /// Builds a graph with `n` vertices (numbered from 1) and the given edges
+    /// `(src, dst)`, all of which weigh 1. See `from_weighted_edges`.
+    pub fn from_edges<I>(n: usize, digraph: bool, edges: I) -> Result<Graph, String>
+        where I: IntoIterator<Item=(isize, isize)>
+    {
+        Self::from_weighted_edges(n, digraph, edges.into_iter().map(|(src, dst)| (src, dst, 1)))
+    }
+}
+
+impl<W> Graph<W> {
+    /// Builds a graph with `n` vertices (numbered from 1) and the given edges
+    /// `(src, dst, weight)`. An edge which is given several times (in either
+    /// orientation for an undirected graph) only appears once with the last
+    /// of its weights. Self loops are accepted, and an error is returned when
+    /// an edge does not connect two vertices of the graph.
+    ///
+    /// The provenance of the resulting graph is an explicit edge list, and
+    /// it allows self loops iff some of the given edges are loops.
+    pub fn from_weighted_edges<I>(n: usize, digraph: bool, edges: I) -> Result<Graph<W>, String>
+        where I: IntoIterator<Item=(isize, isize, W)>
+    {
+        let mut meta = GraphMeta::new("explicit edge list");
+        if digraph {
+            meta = meta.digraph();
+        }
+
+        let mut g = Graph::empty(meta, n);
+        for (src, dst, w) in edges {
+            for v in [src, dst].iter() {
+                if *v < 1 || *v > n as isize {
+                    return Err(format!("the edge ({}, {}) refers to a vertex not in 1..={}", src, dst, n));
+                }
+            }
+            let edge = Edge{src: Vertex{id: src}, dst: Vertex{id: dst}};
+            if edge.is_self_loop() {
+                g.meta.self_loops = true;
+            }
+            if !digraph && g.list.contains_key(&edge.rev()) {
+                g.list.insert(edge.rev(), w);
+            } else {
+                g.list.insert(edge, w);
+            }
+        }
+
+        Ok(g)
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module defines the errors reported when a random model is given
+//! parameters that it cannot honour.
+
+use std::error::Error;
+use std::fmt;
+
+/// The reasons why a graph cannot be generated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphGenError {
+    /// The graph would not have any vertex
+    NoVertex,
+    /// The likelihood of the edges is not a probability (in [0, 1])
+    InvalidProbability(f64),
+    /// The model asks for more edges than the graph can possibly have
+    TooManyEdges {
+        /// The number of edges the model asks for
+        wanted: u128,
+        /// The number of edges the graph can have
+        possible: u128
+    },
+    /// The model asks for fewer edges than a connected graph has
+    TooFewEdges {
+        /// The number of edges the model asks for
+        wanted: u128,
+        /// The number of edges a connected graph has at least
+        needed: u128
+    },
+    /// The constraints put on the graph cannot be satisfied together
+    Conflict(&'static str),
+    /// None of the sampled graphs satisfied the predicate
+    Rejected {
+        /// The number of graphs that were sampled
+        tries: usize
+    }
+}
+
+impl GraphGenError {
+    /// Fails when there is no vertex or when p is not a probability, which
+    /// all the random models check before anything else
+    pub(crate) fn check(n: usize, p: f64) -> Result<(), GraphGenError> {
+        if n == 0 {
+            return Err(GraphGenError::NoVertex);
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(GraphGenError::InvalidProbability(p));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GraphGenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphGenError::NoVertex =>
+                write!(f, "the graph must have at least one vertex"),
+            GraphGenError::InvalidProbability(p) =>
+                write!(f, "the probability {} is not in [0, 1]", p),
+            GraphGenError::TooManyEdges{wanted, possible} =>
+                write!(f, "cannot pick {} edges when at most {} of them can be generated", wanted, possible),
+            GraphGenError::TooFewEdges{wanted, needed} =>
+                write!(f, "{} edges cannot connect the graph: it needs at least {} of them", wanted, needed),
+            GraphGenError::Conflict(why) =>
+                write!(f, "the constraints of the model conflict: {}", why),
+            GraphGenError::Rejected{tries} =>
+                write!(f, "none of the {} sampled graphs satisfied the predicate", tries)
+        }
+    }
+}
+
+impl Error for GraphGenError {}
@@ -0,0 +1,177 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module produces min cost flow instances in the DIMACS format. Each
+//! arc carries a capacity and a cost (drawn from independent distributions)
+//! and the supply of the source is the maximum flow it can send to the sink,
+//! so that the instances are always feasible.
+
+use crate::{Edge, Graph, Vertex, WeightDistribution};
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Write};
+
+/// A min cost flow instance: the flow leaves the first vertex and reaches
+/// the last one
+#[derive(Debug, Clone)]
+pub struct MinCostFlowInstance {
+    /// The digraph whose arcs carry `capacity` and `cost` attributes
+    g     : Graph,
+    /// The amount of flow sent from the source to the sink
+    supply: isize
+}
+impl MinCostFlowInstance {
+    /// Creates the instance on the given graph. Each edge of an undirected
+    /// graph yields the two opposite arcs, and the self loops are dropped.
+    /// The capacity and cost of each arc are drawn from the distributions
+    /// (a negative capacity is raised to 0). An error is returned when the
+    /// graph has fewer than two vertices or a distribution is invalid.
+    pub fn new(g: Graph, capacities: &WeightDistribution, costs: &WeightDistribution) -> Result<Self, String> {
+        if g.n < 2 {
+            return Err("a flow instance needs at least two vertices".to_string());
+        }
+        let mut meta = g.derived_meta("flow network");
+        meta.digraph    = true;
+        meta.self_loops = false;
+        meta.oriented   = false;
+
+        let mut arcs = Graph::empty(meta, g.n);
+        arcs.labels  = g.labels.clone();
+        for (e, w) in g.list.iter().filter(|(e, _)| !e.is_self_loop()) {
+            arcs.list.insert(*e, *w);
+            if !g.meta.digraph {
+                arcs.list.insert(e.rev(), *w);
+            }
+        }
+        arcs.draw_random_attribute("capacity", capacities)?;
+        arcs.draw_random_attribute("cost", costs)?;
+        if let Some(values) = arcs.attrs.get_mut("capacity") {
+            values.values_mut().for_each(|c| *c = (*c).max(0));
+        }
+
+        let mut instance = MinCostFlowInstance{g: arcs, supply: 0};
+        instance.supply  = instance.max_flow();
+        Ok(instance)
+    }
+
+    /// Returns the graph underlying this instance
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+    /// Returns the vertex the flow leaves
+    pub fn source(&self) -> Vertex {
+        Vertex{id: 1}
+    }
+    /// Returns the vertex the flow reaches
+    pub fn sink(&self) -> Vertex {
+        Vertex{id: self.g.n as isize}
+    }
+    /// Returns the amount of flow sent from the source to the sink
+    pub fn supply(&self) -> isize {
+        self.supply
+    }
+
+    /// Returns the sorted arcs of the instance with their capacity and cost
+    fn arcs(&self) -> Vec<(Edge, isize, isize)> {
+        let mut arcs = self.g.list.keys()
+            .map(|e| (*e, self.g.attrs["capacity"][e], self.g.attrs["cost"][e]))
+            .collect::<Vec<(Edge, isize, isize)>>();
+        arcs.sort_unstable();
+        arcs
+    }
+    /// Returns the maximum flow from the source to the sink (Edmonds-Karp)
+    fn max_flow(&self) -> isize {
+        let mut residual = HashMap::new();
+        let mut next     = vec![vec![]; self.g.n + 1];
+        for (e, capacity, _) in self.arcs() {
+            let (u, v) = (e.src.id as usize, e.dst.id as usize);
+            if !residual.contains_key(&(u, v)) && !residual.contains_key(&(v, u)) {
+                next[u].push(v);
+                next[v].push(u);
+            }
+            *residual.entry((u, v)).or_insert(0) += capacity;
+            residual.entry((v, u)).or_insert(0);
+        }
+
+        let (source, sink) = (1, self.g.n);
+        let mut flow = 0;
+        loop {
+            let mut parent = vec![0; self.g.n + 1];
+            let mut queue  = VecDeque::from(vec![source]);
+            parent[source] = source;
+            while let Some(u) = queue.pop_front() {
+                for &v in next[u].iter() {
+                    if parent[v] == 0 && residual[&(u, v)] > 0 {
+                        parent[v] = u;
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if parent[sink] == 0 {
+                return flow;
+            }
+
+            let mut path = vec![];
+            let mut v    = sink;
+            while v != source {
+                path.push((parent[v], v));
+                v = parent[v];
+            }
+            let amount = path.iter().map(|arc| residual[arc]).min().unwrap_or(0);
+            for (u, v) in path {
+                *residual.get_mut(&(u, v)).unwrap() -= amount;
+                *residual.get_mut(&(v, u)).unwrap() += amount;
+            }
+            flow += amount;
+        }
+    }
+
+    /// Writes the instance in the DIMACS min cost flow format: a
+    /// `p min <nodes> <arcs>` header, the `n <id> <supply>` lines of the
+    /// source and sink, and one `a <src> <dst> <low> <cap> <cost>` line per
+    /// arc (all lower bounds are 0).
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let arcs = self.arcs();
+        writeln!(out, "c Min cost flow instance on a {}", self.g.meta.provenance)?;
+        writeln!(out, "c This network has {} nodes and {} arcs", self.g.n, arcs.len())?;
+        writeln!(out, "c The supply of {} is the maximum flow it can send to {}", self.source().id, self.sink().id)?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p min {} {}", self.g.n, arcs.len())?;
+        writeln!(out, "n {} {}", self.source().id, self.supply)?;
+        writeln!(out, "n {} {}", self.sink().id, -self.supply)?;
+        for (e, capacity, cost) in arcs {
+            writeln!(out, "a {} {} 0 {} {}", e.src.id, e.dst.id, capacity, cost)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
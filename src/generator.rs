@@ -0,0 +1,71 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module defines the interface shared by all the random graph models,
+//! so that client code can be written against any of them.
+
+use crate::{ErGenerator, Graph, GraphGenError};
+use rand::Rng;
+
+/// A source of random graphs
+pub trait GraphGenerator {
+    /// Generates a new random graph
+    fn gen(&mut self) -> Graph;
+    /// Returns the name of the random model (e.g. `Erdos-Renyi`)
+    fn name(&self) -> &'static str;
+    /// Returns a short description of the model parameters (e.g. `G(n, p)`)
+    fn parameters(&self) -> String;
+    /// Returns the number of vertices of the generated graphs
+    fn num_vertices(&self) -> usize;
+    /// Tells whether the generated graphs are directed
+    fn is_digraph(&self) -> bool;
+    /// Generates random graphs until one of them satisfies the predicate
+    /// (e.g. "connected with a diameter of at most 6") and returns it. It
+    /// fails when none of the first `max_tries` graphs satisfies it.
+    fn gen_until<F>(&mut self, mut predicate: F, max_tries: usize) -> Result<Graph, GraphGenError>
+        where Self: Sized, F: FnMut(&Graph) -> bool
+    {
+        for _ in 0..max_tries {
+            let g = self.gen();
+            if predicate(&g) {
+                return Ok(g);
+            }
+        }
+        Err(GraphGenError::Rejected{tries: max_tries})
+    }
+}
+
+impl<R: Rng> GraphGenerator for ErGenerator<R> {
+    fn gen(&mut self) -> Graph {
+        ErGenerator::gen(self)
+    }
+    fn name(&self) -> &'static str {
+        "Erdos-Renyi"
+    }
+    fn parameters(&self) -> String {
+        self.model.to_string()
+    }
+    fn num_vertices(&self) -> usize {
+        self.model.n
+    }
+    fn is_digraph(&self) -> bool {
+        self.model.digraph
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module provides the geometric primitives used by the generators which
+//! embed their vertices in the plane.
+
+use rand::Rng;
+use rand::distributions::{Distribution, Uniform};
+
+/// A point in the euclidean plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64
+}
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Point{x, y}
+    }
+    /// Returns the euclidean distance between this point and the other one
+    pub fn distance(self, other: Point) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+    /// Samples `n` points uniformly at random in the `side x side` square.
+    /// The coordinates are integers so that the points can be written in
+    /// the formats (TSPLIB, CVRPLIB) that expect them to be.
+    pub fn random_cloud(n: usize, side: usize) -> Vec<Point> {
+        Self::random_cloud_with_rng(n, side, &mut crate::random::rng())
+    }
+    /// Same as `random_cloud` but the points are drawn from the given rng
+    pub fn random_cloud_with_rng<R: Rng>(n: usize, side: usize, rng: &mut R) -> Vec<Point> {
+        let dist = Uniform::new_inclusive(0, side);
+
+        (0..n).map(|_| Point::new(dist.sample(rng) as f64, dist.sample(rng) as f64))
+            .collect()
+    }
+}
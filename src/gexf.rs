@@ -0,0 +1,87 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the GEXF format so that they can be opened
+//! in Gephi.
+
+use crate::{Graph, Point, Vertex};
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Writes the graph in the GEXF (1.2) format. The weights of the edges
+    /// are preserved.
+    pub fn to_gexf(&self) -> String {
+        crate::written(|out| self.write_gexf(out))
+    }
+
+    /// Same as `to_gexf` but streams the output to the given writer
+    pub fn write_gexf<W: Write>(&self, out: W) -> io::Result<()> {
+        self.gexf(out, None)
+    }
+
+    /// Writes the graph in the GEXF (1.2) format where the vertex `v` is
+    /// positioned at `positions[v-1]` (as a viz attribute)
+    pub fn to_gexf_with_positions(&self, positions: &[Point]) -> String {
+        crate::written(|out| self.write_gexf_with_positions(out, positions))
+    }
+
+    /// Same as `to_gexf_with_positions` but streams the output to the given
+    /// writer
+    pub fn write_gexf_with_positions<W: Write>(&self, out: W, positions: &[Point]) -> io::Result<()> {
+        self.gexf(out, Some(positions))
+    }
+
+    fn gexf<W: Write>(&self, mut out: W, positions: Option<&[Point]>) -> io::Result<()> {
+        let gtype = if self.meta.digraph { "directed" } else { "undirected" };
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(out, r#"<gexf xmlns="http://www.gexf.net/1.2draft" xmlns:viz="http://www.gexf.net/1.2draft/viz" version="1.2">"#)?;
+        writeln!(out, "  <meta>")?;
+        writeln!(out, "    <creator>graph_gen: https://github.com/xgillard/graph_gen</creator>")?;
+        writeln!(out, "    <description>Graph from {}</description>", self.meta.provenance.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"))?;
+        writeln!(out, "  </meta>")?;
+        writeln!(out, r#"  <graph mode="static" defaultedgetype="{}">"#, gtype)?;
+
+        writeln!(out, "    <nodes>")?;
+        for v in 1..=self.n {
+            let label = self.label_or_id(Vertex{id: v as isize})
+                .replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;");
+            let id = self.index(Vertex{id: v as isize});
+            match positions.and_then(|p| p.get(v - 1)) {
+                None    => writeln!(out, r#"      <node id="{}" label="{}"/>"#, id, label)?,
+                Some(p) => {
+                    writeln!(out, r#"      <node id="{}" label="{}">"#, id, label)?;
+                    writeln!(out, r#"        <viz:position x="{}" y="{}" z="0.0"/>"#, p.x, p.y)?;
+                    writeln!(out, "      </node>")?;
+                }
+            }
+        }
+        writeln!(out, "    </nodes>")?;
+
+        writeln!(out, "    <edges>")?;
+        for (i, (edge, w)) in self.list.iter().enumerate() {
+            writeln!(out, r#"      <edge id="{}" source="{}" target="{}" weight="{}"/>"#, i, self.index(edge.src), self.index(edge.dst), w)?;
+        }
+        writeln!(out, "    </edges>")?;
+
+        writeln!(out, "  </graph>")?;
+        writeln!(out, "</gexf>")
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the GML (graph modelling language) format
+//! which is read by many network analysis tools.
+
+use crate::{Graph, Vertex};
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Writes the graph in the GML format: one `node` record per vertex and
+    /// one `edge` record (carrying a `weight` attribute) per edge.
+    pub fn to_gml(&self) -> String {
+        crate::written(|out| self.write_gml(out))
+    }
+
+    /// Same as `to_gml` but streams the output to the given writer
+    pub fn write_gml<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "graph [")?;
+        writeln!(out, "  comment \"Graph from {} generated w/ graph_gen: https://github.com/xgillard/graph_gen\"", self.meta.provenance.replace('&', "&amp;").replace('"', "&quot;"))?;
+        writeln!(out, "  directed {}", self.meta.digraph as u8)?;
+        for v in 1..=self.n {
+            writeln!(out, "  node [")?;
+            writeln!(out, "    id {}", self.index(Vertex{id: v as isize}))?;
+            writeln!(out, "    label \"{}\"", self.label_or_id(Vertex{id: v as isize}).replace('&', "&amp;").replace('"', "&quot;"))?;
+            if let Some(class) = self.class(Vertex{id: v as isize}) {
+                writeln!(out, "    class {}", class)?;
+            }
+            if let Some(weight) = self.vertex_weight(Vertex{id: v as isize}) {
+                writeln!(out, "    weight {}", weight)?;
+            }
+            writeln!(out, "  ]")?;
+        }
+        for (edge, w) in self.list.iter() {
+            writeln!(out, "  edge [")?;
+            writeln!(out, "    source {}", self.index(edge.src))?;
+            writeln!(out, "    target {}", self.index(edge.dst))?;
+            writeln!(out, "    weight {}", w)?;
+            for (name, values) in self.attrs.iter() {
+                if let Some(v) = values.get(edge) {
+                    writeln!(out, "    {} {}", name, v)?;
+                }
+            }
+            writeln!(out, "  ]")?;
+        }
+        writeln!(out, "]")?;
+
+        Ok(())
+    }
+}
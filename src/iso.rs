@@ -0,0 +1,203 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module produces graph isomorphism (and graph alignment) benchmarks.
+//! Each instance comprises a random graph, a randomly relabeled copy of that
+//! graph (whose edges can optionally be perturbed) and the ground truth
+//! mapping between the two.
+
+use crate::{Edge, Graph, Vertex};
+use rand::distributions::{Distribution, Uniform};
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/// A pair of (nearly) isomorphic graphs along with the mapping that relates
+/// them. The vertex `v` of the original graph corresponds to the vertex
+/// `mapping[v-1]` of the copy.
+#[derive(Debug, Clone)]
+pub struct IsoPair {
+    g      : Graph,
+    h      : Graph,
+    mapping: Vec<isize>,
+    perturbations: usize
+}
+impl IsoPair {
+    /// The number of random edges tried before looking at all the free ones
+    /// when picking a replacement edge
+    const PERTURBATION_TRIES: usize = 64;
+
+    /// Creates a pair from the given graph and a randomly permuted copy of it
+    /// where `perturbations` random edges have been replaced by other random
+    /// edges. The replacement edges reuse the weights of the removed ones.
+    pub fn new(g: Graph, perturbations: usize) -> Self {
+        let mut rng     = crate::random::rng();
+        let mut mapping = (1..=g.n as isize).collect::<Vec<isize>>();
+        mapping.shuffle(&mut rng);
+
+        let mut h = Graph::empty(g.meta.clone(), g.n);
+        for (edge, w) in g.list.iter() {
+            let src = Vertex{id: mapping[edge.src.id as usize - 1]};
+            let dst = Vertex{id: mapping[edge.dst.id as usize - 1]};
+            h.list.insert(Edge{src, dst}, *w);
+        }
+
+        let perturbations = perturbations.min(h.list.len());
+        let mut edges     = h.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        edges.shuffle(&mut rng);
+
+        // a removed edge may not come back, otherwise it would not be perturbed
+        let removed     = edges[..perturbations].iter().copied().collect::<HashSet<Edge>>();
+        let mut weights = vec![];
+        for edge in edges[..perturbations].iter() {
+            weights.push(h.list.remove(edge).unwrap());
+        }
+
+        let vertices = Uniform::new_inclusive(1, g.n as isize);
+        while let Some(w) = weights.pop() {
+            let mut edge = None;
+            for _ in 0..Self::PERTURBATION_TRIES {
+                let src       = Vertex{id: vertices.sample(&mut rng)};
+                let dst       = Vertex{id: vertices.sample(&mut rng)};
+                let candidate = Edge{src, dst};
+                if Self::is_free(&h, &removed, candidate) {
+                    edge = Some(candidate);
+                    break;
+                }
+            }
+            if edge.is_none() {
+                let mut free = vec![];
+                for src in h.vertices() {
+                    let first = if h.meta.digraph { 1 } else { src.id };
+                    for dst in first..=h.n as isize {
+                        let candidate = Edge{src, dst: Vertex{id: dst}};
+                        if Self::is_free(&h, &removed, candidate) {
+                            free.push(candidate);
+                        }
+                    }
+                }
+                edge = free.choose(&mut rng).copied();
+            }
+            match edge {
+                // there is no room left for any other edge
+                None       => break,
+                Some(edge) => { h.list.insert(edge, w); }
+            }
+        }
+
+        IsoPair{g, h, mapping, perturbations}
+    }
+    /// Returns true iff the edge can replace a removed one in `h`: it is
+    /// neither in `h` nor removed from it (in either orientation, unless
+    /// the graph allows anti-parallel arcs) and it is no forbidden self loop
+    fn is_free(h: &Graph, removed: &HashSet<Edge>, edge: Edge) -> bool {
+        if edge.is_self_loop() && !h.meta.self_loops {
+            return false;
+        }
+        let taken = |e: Edge| removed.contains(&e) || h.list.contains_key(&e);
+        !taken(edge) && (h.meta.allows_antiparallel_arcs() || !taken(edge.rev()))
+    }
+
+    /// Returns the original graph
+    pub fn first(&self) -> &Graph {
+        &self.g
+    }
+    /// Returns the relabeled (and perturbed) copy
+    pub fn second(&self) -> &Graph {
+        &self.h
+    }
+    /// Returns the ground truth mapping: the vertex `v` of the first graph
+    /// corresponds to the vertex `mapping()[v-1]` of the second one
+    pub fn mapping(&self) -> &[isize] {
+        &self.mapping
+    }
+
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let gtype = if self.g.meta.digraph    { "digraph" } else {"graph"};
+        let loops = if self.g.meta.self_loops { "" }        else { " NOT"};
+        writeln!(out, "c Pseudo-random isomorphism instance on a {} from {}", gtype, self.g.meta.provenance)?;
+        writeln!(out, "c it was generated to{} allow self loops", loops)?;
+        writeln!(out, "c The second graph is a relabeled copy of the first where {} edges were perturbed", self.perturbations)?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Each graph starts with its '<vertices> <edges>' header")?;
+        writeln!(out, "c The mapping lines read 'm <vertex in first> <vertex in second>'")?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+
+        for (name, graph) in [("First", &self.g), ("Second", &self.h)].iter() {
+            writeln!(out, "c {} graph", name)?;
+            writeln!(out, "{} {}", graph.n, graph.list.len())?;
+            for (edge, w) in graph.list.iter() {
+                writeln!(out, "{} {} {}", edge.src.id, edge.dst.id, w)?;
+            }
+        }
+
+        writeln!(out, "c Ground truth mapping")?;
+        for (i, v) in self.mapping.iter().enumerate() {
+            writeln!(out, "m {} {}", i + 1, v)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        crate::written(|out| self.write_dot(out))
+    }
+    pub fn write_dot<W: Write>(&self, mut out: W) -> io::Result<()> {
+        self.g.write_dot(&mut out)?;
+        self.h.write_dot(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErModel;
+
+    #[test]
+    fn perturbing_a_dense_digraph_terminates() {
+        let g    = ErModel::new(4, 0.9).digraph().generator_with_seed(1).gen();
+        let m    = g.num_edges();
+        let pair = IsoPair::new(g, 1);
+        assert_eq!(m, pair.h.num_edges());
+    }
+
+    #[test]
+    fn perturbing_every_arc_of_a_digraph_with_loops_terminates() {
+        let g    = ErModel::new(4, 0.5).digraph().with_self_loops().generator_with_seed(1).gen();
+        let m    = g.num_edges();
+        let pair = IsoPair::new(g, 100);
+        // the removed arcs may not come back
+        assert_eq!(m.min(16 - m), pair.h.num_edges());
+    }
+
+    #[test]
+    fn perturbed_oriented_graphs_have_no_antiparallel_arcs() {
+        let g    = ErModel::new(6, 0.5).digraph().oriented().generator_with_seed(2).gen();
+        let pair = IsoPair::new(g, 100);
+        assert!(pair.h.edges().all(|e| !pair.h.list.contains_key(&e.rev())));
+    }
+}
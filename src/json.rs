@@ -0,0 +1,130 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs as JSON documents that can directly be loaded
+//! by d3.js or by the json_graph readers of networkx (node-link and
+//! adjacency layouts).
+
+use crate::{Graph, Vertex};
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Returns the JSON object describing how the graph was produced
+    fn json_metadata(&self) -> String {
+        let provenance = self.meta.provenance.replace('\\', "\\\\").replace('"', "\\\"");
+        format!(r#"{{"provenance": "{}", "n": {}, "self_loops": {}, "generator": "graph_gen: https://github.com/xgillard/graph_gen"}}"#,
+            provenance, self.n, self.meta.self_loops)
+    }
+    /// Returns the JSON object describing the vertex `v` (its id, along with
+    /// its label, class and weight when it has some)
+    fn json_node(&self, v: usize) -> String {
+        let vertex = Vertex{id: v as isize};
+        let mut node = format!(r#"{{"id": {}"#, self.index(vertex));
+        if let Some(label) = self.label(vertex) {
+            node.push_str(&format!(r#", "label": "{}""#, label.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+        if let Some(class) = self.class(vertex) {
+            node.push_str(&format!(r#", "class": {}"#, class));
+        }
+        if let Some(weight) = self.vertex_weight(vertex) {
+            node.push_str(&format!(r#", "weight": {}"#, weight));
+        }
+        node.push('}');
+        node
+    }
+
+    /// Writes the graph in the standard node-link JSON structure:
+    /// `{"nodes": [{"id": ..}, ..], "links": [{"source": .., "target": .., "weight": ..}, ..]}`
+    /// along with the `directed`, `multigraph` and `graph` (provenance metadata)
+    /// entries expected by `networkx.readwrite.json_graph.node_link_graph`.
+    /// The links also carry the additional attributes of the edges.
+    pub fn to_json(&self) -> String {
+        crate::written(|out| self.write_json(out))
+    }
+
+    /// Same as `to_json` but streams the output to the given writer
+    pub fn write_json<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{{")?;
+        writeln!(out, r#"  "directed": {},"#, self.meta.digraph)?;
+        writeln!(out, r#"  "multigraph": false,"#)?;
+        writeln!(out, r#"  "graph": {},"#, self.json_metadata())?;
+
+        writeln!(out, r#"  "nodes": ["#)?;
+        for v in 1..=self.n {
+            let sep = if v < self.n { "," } else { "" };
+            writeln!(out, "    {}{}", self.json_node(v), sep)?;
+        }
+        writeln!(out, "  ],")?;
+
+        writeln!(out, r#"  "links": ["#)?;
+        for (i, (e, w)) in self.list.iter().enumerate() {
+            let sep = if i + 1 < self.list.len() { "," } else { "" };
+            let attributes = self.attrs.iter()
+                .filter_map(|(name, values)| values.get(e).map(|v| format!(r#", "{}": {}"#, name, v)))
+                .collect::<String>();
+            writeln!(out, r#"    {{"source": {}, "target": {}, "weight": {}{}}}{}"#, self.index(e.src), self.index(e.dst), w, attributes, sep)?;
+        }
+        writeln!(out, "  ]")?;
+        writeln!(out, "}}")
+    }
+
+    /// Writes the graph in the adjacency JSON layout of networkx, so that
+    /// `networkx.adjacency_graph(json.load(f))` rebuilds it. The `adjacency`
+    /// list gives, for each of the `nodes` (in that order), the list of its
+    /// neighbours: `[{"id": .., "weight": ..}, ..]`. In an undirected graph,
+    /// each edge is listed at both of its ends.
+    pub fn to_adjacency_json(&self) -> String {
+        crate::written(|out| self.write_adjacency_json(out))
+    }
+
+    /// Same as `to_adjacency_json` but streams the output to the given writer
+    pub fn write_adjacency_json<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let mut adj = (0..self.n).map(|_| vec![]).collect::<Vec<Vec<(isize, &T)>>>();
+        for (e, w) in self.list.iter() {
+            adj[e.src.id as usize - 1].push((self.index(e.dst), w));
+            if !self.meta.digraph && !e.is_self_loop() {
+                adj[e.dst.id as usize - 1].push((self.index(e.src), w));
+            }
+        }
+
+        writeln!(out, "{{")?;
+        writeln!(out, r#"  "directed": {},"#, self.meta.digraph)?;
+        writeln!(out, r#"  "multigraph": false,"#)?;
+        writeln!(out, r#"  "graph": {},"#, self.json_metadata())?;
+
+        let nodes = (1..=self.n)
+            .map(|v| self.json_node(v))
+            .collect::<Vec<String>>();
+        writeln!(out, r#"  "nodes": [{}],"#, nodes.join(", "))?;
+
+        writeln!(out, r#"  "adjacency": ["#)?;
+        for (i, neighbours) in adj.iter_mut().enumerate() {
+            neighbours.sort_unstable_by_key(|(v, _)| *v);
+            let entries = neighbours.iter()
+                .map(|(v, w)| format!(r#"{{"id": {}, "weight": {}}}"#, v, w))
+                .collect::<Vec<String>>();
+            let sep = if i + 1 < self.n { "," } else { "" };
+            writeln!(out, "    [{}]{}", entries.join(", "), sep)?;
+        }
+        writeln!(out, "  ]")?;
+        writeln!(out, "}}")
+    }
+}
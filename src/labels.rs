@@ -0,0 +1,132 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module lets the vertices of a graph carry a (string) label, an
+//! (integer) class and an (integer) weight. All are optional: they can be
+//! set explicitly or picked at random, and are written by the formats which
+//! support them (dot, json, gml, gexf and pajek for the labels and classes;
+//! json, gml, metis and the weighted clique dimacs for the weights).
+
+use crate::{Graph, Vertex};
+use rand::distributions::{Distribution, Uniform};
+use std::collections::HashMap;
+
+/// The labels, classes and weights of the vertices (indexed by their ids)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VertexLabels {
+    labels : HashMap<isize, String>,
+    classes: HashMap<isize, isize>,
+    weights: HashMap<isize, isize>
+}
+impl VertexLabels {
+    /// Copies the label, class and weight of the vertex `v` (if it has some)
+    /// to the vertex `to` of the other labels
+    pub(crate) fn copy(&self, v: isize, other: &mut VertexLabels, to: isize) {
+        if let Some(label) = self.labels.get(&v) {
+            other.labels.insert(to, label.clone());
+        }
+        if let Some(class) = self.classes.get(&v) {
+            other.classes.insert(to, *class);
+        }
+        if let Some(weight) = self.weights.get(&v) {
+            other.weights.insert(to, *weight);
+        }
+    }
+}
+
+impl<W> Graph<W> {
+    /// Gives the label to the vertex `v`
+    pub fn set_label(&mut self, v: Vertex, label: String) {
+        self.labels.labels.insert(v.id, label);
+    }
+    /// Returns the label of the vertex `v` (if it has one)
+    pub fn label(&self, v: Vertex) -> Option<&str> {
+        self.labels.labels.get(&v.id).map(|l| l.as_str())
+    }
+    /// Puts the vertex `v` in the given class
+    pub fn set_class(&mut self, v: Vertex, class: isize) {
+        self.labels.classes.insert(v.id, class);
+    }
+    /// Returns the class of the vertex `v` (if it has one)
+    pub fn class(&self, v: Vertex) -> Option<isize> {
+        self.labels.classes.get(&v.id).copied()
+    }
+    /// Gives the weight to the vertex `v`
+    pub fn set_vertex_weight(&mut self, v: Vertex, weight: isize) {
+        self.labels.weights.insert(v.id, weight);
+    }
+    /// Returns the weight of the vertex `v` (if it has one)
+    pub fn vertex_weight(&self, v: Vertex) -> Option<isize> {
+        self.labels.weights.get(&v.id).copied()
+    }
+    /// Returns the weights of all vertices (those which have no weight
+    /// weigh 1), or nothing when no vertex has a weight
+    pub(crate) fn vertex_weights(&self) -> Option<Vec<isize>> {
+        if self.labels.weights.is_empty() {
+            None
+        } else {
+            Some(self.vertices().map(|v| self.vertex_weight(v).unwrap_or(1)).collect())
+        }
+    }
+    /// Gives each vertex a label picked at random among the candidates
+    /// (nothing happens when there is no candidate)
+    pub fn pluck_random_labels(&mut self, from: &[String]) {
+        if from.is_empty() {
+            return;
+        }
+        let mut rng = crate::random::rng();
+        let dist= Uniform::new(0, from.len());
+
+        for v in 1..=self.n as isize {
+            self.labels.labels.insert(v, from[dist.sample(&mut rng)].clone());
+        }
+    }
+    /// Puts each vertex in a class picked at random among the candidates
+    /// (nothing happens when there is no candidate)
+    pub fn pluck_random_classes(&mut self, from: &[isize]) {
+        if from.is_empty() {
+            return;
+        }
+        let mut rng = crate::random::rng();
+        let dist= Uniform::new(0, from.len());
+
+        for v in 1..=self.n as isize {
+            self.labels.classes.insert(v, from[dist.sample(&mut rng)]);
+        }
+    }
+    /// Gives each vertex a weight picked at random among the candidates
+    /// (nothing happens when there is no candidate)
+    pub fn pluck_random_vertex_weights(&mut self, from: &[isize]) {
+        if from.is_empty() {
+            return;
+        }
+        let mut rng = crate::random::weight_rng();
+        let dist= Uniform::new(0, from.len());
+
+        for v in 1..=self.n as isize {
+            self.labels.weights.insert(v, from[dist.sample(&mut rng)]);
+        }
+    }
+    /// Returns the label of the vertex `v`, or its number when it has no
+    /// label
+    pub(crate) fn label_or_id(&self, v: Vertex) -> String {
+        self.label(v).map_or_else(|| self.index(v).to_string(), |l| l.to_string())
+    }
+}
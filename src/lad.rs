@@ -0,0 +1,58 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the LAD format that is natively understood
+//! by the subgraph isomorphism solvers (LAD, Glasgow).
+
+use crate::Graph;
+use std::io::{self, Write};
+
+impl<T> Graph<T> {
+    /// Writes the graph in the LAD format: the first line gives the number
+    /// of vertices, and then each line gives the degree of a vertex followed
+    /// by the list of its neighbours. The vertices are numbered from zero.
+    /// In an undirected graph, each edge is listed at both of its ends.
+    pub fn to_lad(&self) -> String {
+        crate::written(|out| self.write_lad(out))
+    }
+
+    /// Same as `to_lad` but streams the output to the given writer
+    pub fn write_lad<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let mut succ = vec![vec![]; self.n];
+        for edge in self.list.keys() {
+            succ[edge.src.id as usize - 1].push(edge.dst.id - 1);
+            if !self.meta.digraph && !edge.is_self_loop() {
+                succ[edge.dst.id as usize - 1].push(edge.src.id - 1);
+            }
+        }
+
+        writeln!(out, "{}", self.n)?;
+        for neighbours in succ.iter_mut() {
+            neighbours.sort_unstable();
+            let mut line = neighbours.len().to_string();
+            for v in neighbours.iter() {
+                line.push_str(&format!(" {}", v));
+            }
+            writeln!(out, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
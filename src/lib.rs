@@ -24,10 +24,107 @@
 
 extern crate rand;
 
-use rand::thread_rng;
+use rand::{Rng, SeedableRng};
 use rand::distributions::{Distribution, Uniform};
-use rand::rngs::ThreadRng;
+use rand::rngs::StdRng;
+use rand::seq::index;
 use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::BuildHasherDefault;
+use std::io::{self, Write};
+use std::sync::OnceLock;
+
+mod error;
+mod random;
+mod generator;
+mod edge_iter;
+mod bernoulli;
+mod dense;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod connectivity;
+mod constraints;
+mod certificate;
+mod planted;
+mod adjacency;
+mod edge_list;
+mod parse;
+mod mutation;
+mod labels;
+mod attributes;
+mod transform;
+mod minor;
+mod components;
+mod stats;
+mod distance;
+mod properties;
+mod comparison;
+mod simplify;
+mod orientation;
+mod rewire;
+mod weights;
+mod distribution;
+mod coloring;
+mod qubo;
+mod lp;
+mod iso;
+mod subiso;
+mod lad;
+mod tsp;
+mod geometry;
+mod cvrp;
+mod matching;
+mod flow;
+mod metis;
+mod ddo;
+mod clique_cover;
+mod pace;
+mod gexf;
+mod mtx;
+mod json;
+mod csv;
+mod pajek;
+mod csr;
+mod matrix;
+mod nauty;
+mod gml;
+mod tgf;
+mod snap;
+mod gzip;
+mod dot;
+mod dimacs;
+
+pub use error::GraphGenError;
+pub use random::{rng, seed, seed_weights, weight_rng};
+pub use generator::GraphGenerator;
+pub use edge_iter::EdgeIter;
+pub use connectivity::Connectivity;
+pub use certificate::Certificate;
+use constraints::Sample;
+use planted::Planted;
+pub use simplify::MergePolicy;
+pub use distribution::WeightDistribution;
+pub use coloring::{ColoringSatGraph, PlantedColoring};
+pub use qubo::MaxCutQubo;
+pub use lp::{IlpGraph, IlpProblem};
+pub use iso::IsoPair;
+pub use subiso::SubIsoPair;
+pub use tsp::AtspInstance;
+pub use geometry::Point;
+pub use cvrp::CvrpInstance;
+pub use matching::MatchingInstance;
+pub use flow::MinCostFlowInstance;
+pub use metis::MetisGraph;
+pub use ddo::DdoPreset;
+pub use clique_cover::CliqueCoverInstance;
+pub use pace::{PartialKTree, PlantedFeedbackSet};
+pub use csr::CSR_MAGIC;
+pub use gzip::gzipped;
+pub use dot::DotStyle;
+pub use dimacs::{DimacsHeader, DimacsStyle};
+pub use stats::GraphStats;
+pub use comparison::Comparison;
 
 /// The configuration of an Erdos-Renyi G(n, p) model.
 #[derive(Debug, Clone, Copy)]
@@ -39,46 +136,384 @@ pub struct ErModel {
     /// this flag is true
     digraph: bool,
     /// Allow self loops
-    self_loops: bool
+    self_loops: bool,
+    /// The seed of the generator (when the graphs were generated w/ a seed)
+    seed: Option<u64>,
+    /// Number the vertices of the generated graphs from zero in the outputs
+    zero_indexed: bool,
+    /// How the connectivity of the generated graphs is guaranteed (if it is)
+    connectivity: Option<Connectivity>,
+    /// Attach an edge to each vertex left isolated
+    no_isolated: bool,
+    /// The maximum degree of the vertices (if it is bounded)
+    max_degree: Option<usize>,
+    /// The minimum degree of the vertices (if it is bounded)
+    min_degree: Option<usize>,
+    /// The length of the shortest cycle allowed (if it is bounded)
+    girth: Option<usize>,
+    /// The structure planted in the generated graphs (if any)
+    planted: Option<Planted>,
+    /// The likelihood of the self loops (when it differs from p)
+    loop_p: Option<f64>,
+    /// Forbid the anti-parallel arcs in a digraph
+    oriented: bool,
+    /// Include each candidate edge independently (rather than picking a
+    /// fixed number of edges)
+    bernoulli: bool
 }
 impl ErModel {
     pub fn new(n: usize, p: f64) -> Self {
-        ErModel {n, p, self_loops: false, digraph: false}
+        ErModel {n, p, self_loops: false, digraph: false, seed: None, zero_indexed: false, connectivity: None, no_isolated: false, max_degree: None, min_degree: None, girth: None, planted: None, loop_p: None, oriented: false, bernoulli: false}
+    }
+    /// Same as `new` but fails when there is no vertex or when p is not a
+    /// probability
+    pub fn try_new(n: usize, p: f64) -> Result<Self, GraphGenError> {
+        GraphGenError::check(n, p)?;
+        Ok(ErModel::new(n, p))
     }
     pub fn digraph(self) -> Self {
-        ErModel{n: self.n, p: self.p, self_loops: self.self_loops, digraph: true}
+        ErModel{digraph: true, ..self}
+    }
+    /// Forbids the anti-parallel arcs (`u -> v` and `v -> u`) in the generated
+    /// digraphs, which are then orientations of simple graphs. This has no
+    /// effect on undirected graphs.
+    pub fn oriented(self) -> Self {
+        ErModel{oriented: true, ..self}
     }
     pub fn with_self_loops(self) -> Self {
-        ErModel{n: self.n, p: self.p, self_loops: true, digraph: self.digraph}
+        ErModel{self_loops: true, ..self}
+    }
+    /// Allows self loops, but picks them with their own likelihood `q`
+    /// (rather than p): about `q n` random vertices get a loop, while the
+    /// other edges are picked with likelihood p among the pairs of distinct
+    /// vertices. Loops can then be rare without being impossible.
+    pub fn with_loop_probability(self, q: f64) -> Self {
+        ErModel{self_loops: true, loop_p: Some(q), ..self}
     }
-    /// returns a new generator for the given model
+    /// Numbers the vertices of the generated graphs from zero (rather than
+    /// one) in the outputs which have no fixed convention
+    pub fn zero_indexed(self) -> Self {
+        ErModel{zero_indexed: true, ..self}
+    }
+    /// returns a new generator for the given model. It panics when the model
+    /// is invalid (see `try_generator`).
     pub fn generator(self) -> ErGenerator {
-        ErGenerator::new(self)
+        self.try_generator().expect("invalid Erdos-Renyi model")
+    }
+    /// returns a new generator for the given model whose output is entirely
+    /// determined by the given seed. The seed is recorded in the model of
+    /// the generated graphs (and hence in their output) so that they can be
+    /// regenerated. It panics when the model is invalid.
+    pub fn generator_with_seed(self, seed: u64) -> ErGenerator {
+        self.try_generator_with_seed(seed).expect("invalid Erdos-Renyi model")
+    }
+    /// returns a new generator for the given model which draws its random
+    /// numbers from the given rng. It panics when the model is invalid.
+    pub fn generator_with_rng<R: Rng>(self, rng: R) -> ErGenerator<R> {
+        self.try_generator_with_rng(rng).expect("invalid Erdos-Renyi model")
+    }
+    /// Same as `generator` but fails when the model is invalid
+    pub fn try_generator(self) -> Result<ErGenerator, GraphGenError> {
+        self.try_generator_with_rng(random::rng())
+    }
+    /// Same as `generator_with_seed` but fails when the model is invalid
+    pub fn try_generator_with_seed(self, seed: u64) -> Result<ErGenerator, GraphGenError> {
+        ErModel{seed: Some(seed), ..self}.try_generator_with_rng(StdRng::seed_from_u64(seed))
+    }
+    /// Same as `generator_with_rng` but fails when the model is invalid
+    pub fn try_generator_with_rng<R: Rng>(self, rng: R) -> Result<ErGenerator<R>, GraphGenError> {
+        self.validate()?;
+        Ok(ErGenerator::new(self, rng))
+    }
+    /// Checks that graphs can be generated with this model: it must have at
+    /// least one vertex, p (and the likelihood of the loops) must be a
+    /// probability, and the number of edges to
+    /// pick must not exceed the number of possible edges. When the graphs are
+    /// resampled until they are connected, there must be enough edges to
+    /// connect them; an oriented digraph with two vertices cannot be strongly
+    /// connected; a single vertex can only have an incident edge if self
+    /// loops are allowed; the maximum degree must be high enough for the
+    /// vertices to hold the edges (and to connect them); and the minimum
+    /// degree can neither exceed the maximum degree nor the number of the
+    /// other vertices. A triangle-free graph has at most n^2/4 edges (twice
+    /// as many arcs in a digraph with anti-parallel arcs), and a
+    /// Hamiltonian cycle cannot be shorter than the girth. Finally, the
+    /// planted structure must fit in the graph (see `validate_planted`).
+    pub fn validate(self) -> Result<(), GraphGenError> {
+        ErModel::try_new(self.n, self.p)?;
+        if let Some(q) = self.loop_p.filter(|q| !(0.0..=1.0).contains(q)) {
+            return Err(GraphGenError::InvalidProbability(q));
+        }
+
+        let possible = self.nb_possible_edges();
+        let wanted   = (self.p * possible as f64).round() as u128;
+        if wanted > possible || wanted > usize::MAX as u128 {
+            return Err(GraphGenError::TooManyEdges{wanted, possible: possible.min(usize::MAX as u128)});
+        }
+        let needed = self.n as u128 - 1;
+        if self.connectivity == Some(Connectivity::Resample) && wanted < needed {
+            return Err(GraphGenError::TooFewEdges{wanted, needed});
+        }
+        // a cycle through two vertices needs anti-parallel arcs
+        if self.connectivity == Some(Connectivity::HamiltonianCycle) && self.digraph && self.oriented && self.n == 2 {
+            return Err(GraphGenError::TooManyEdges{wanted: 2, possible: 1});
+        }
+        if self.no_isolated && self.n == 1 && !self.self_loops {
+            return Err(GraphGenError::Conflict("a single vertex cannot have an incident edge without self loops"));
+        }
+        if let Some(g) = self.girth {
+            if g > 3 {
+                let pairs    = self.n as u128 * self.n as u128 / 4;
+                let possible = if self.has_antiparallel_arcs() { 2 * pairs } else { pairs }.min(possible);
+                if wanted > possible {
+                    return Err(GraphGenError::TooManyEdges{wanted, possible});
+                }
+            }
+            if self.connectivity == Some(Connectivity::HamiltonianCycle) && self.n > 2 && self.n < g {
+                return Err(GraphGenError::Conflict("a Hamiltonian cycle is shorter than the girth"));
+            }
+        }
+        if let Some(planted) = self.planted {
+            self.validate_planted(planted, wanted, possible)?;
+        }
+        if let Some(d) = self.min_degree {
+            if d >= self.n {
+                return Err(GraphGenError::Conflict("the minimum degree must be lower than the number of vertices"));
+            }
+            if self.max_degree.is_some_and(|max| d > max) {
+                return Err(GraphGenError::Conflict("the minimum degree cannot exceed the maximum degree"));
+            }
+        }
+        if let Some(d) = self.max_degree {
+            let possible = (self.n as u128 * d as u128 / 2).min(possible);
+            if wanted > possible {
+                return Err(GraphGenError::TooManyEdges{wanted, possible});
+            }
+            let needed = if self.n > 2 { 2 } else { self.n - 1 };
+            if self.connectivity.is_some() && d < needed {
+                return Err(GraphGenError::Conflict("the vertices cannot be connected with a degree below 2"));
+            }
+            if self.no_isolated && d == 0 {
+                return Err(GraphGenError::Conflict("the vertices cannot have an incident edge with a degree of 0"));
+            }
+        }
+        Ok(())
+    }
+    /// returns the seed of the generator (if the graph was generated w/ a
+    /// seed)
+    pub fn seed(self) -> Option<u64> {
+        self.seed
     }
 
     /// returns the number of edges if the graph were full mesh
     fn nb_possible_edges(self) -> u128 {
-        let sources = self.n as u128;
-        let dests   = if self.self_loops { self.n } else { self.n - 1 } as u128;
-
-        if self.digraph {
-            sources * dests
-        } else {
-            (sources * dests) / 2
-        }
+        nb_possible_edges(self.n, self.has_antiparallel_arcs(), self.picks_loops())
+    }
+    /// returns true iff both the arcs `u -> v` and `v -> u` can be picked
+    fn has_antiparallel_arcs(self) -> bool {
+        self.digraph && !self.oriented
     }
     /// returns the number of edges that should be sampled so that each of the
-    /// candidate edges has a likelihood of p.
+    /// candidate edges has a likelihood of p (this is the expected number of
+    /// edges in G(n, p) mode).
     fn nb_edges_to_pick(self) -> usize {
         (self.p * self.nb_possible_edges() as f64).round() as usize
     }
+    /// returns true iff the self loops are picked along with the other edges
+    /// (with likelihood p)
+    fn picks_loops(self) -> bool {
+        self.self_loops && self.loop_p.is_none()
+    }
+    /// returns the number of self loops that should be sampled on their own
+    /// so that each vertex has a loop with the likelihood of the loops
+    fn nb_loops_to_pick(self) -> usize {
+        self.loop_p.map_or(0, |q| (q * self.n as f64).round() as usize)
+    }
+}
+
+/// Writes the model as `G(n, p)` followed by its constraints and the seed
+/// (if it is known)
+impl fmt::Display for ErModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "G({}, {})", self.n, self.p)?;
+        let mut constraints = vec![];
+        match self.connectivity {
+            Some(Connectivity::HamiltonianCycle) if self.digraph => constraints.push("strongly connected".to_string()),
+            Some(_) => constraints.push("connected".to_string()),
+            None    => ()
+        }
+        if self.no_isolated {
+            constraints.push("no isolated vertex".to_string());
+        }
+        if let Some(d) = self.max_degree {
+            constraints.push(format!("max degree {}", d));
+        }
+        if let Some(d) = self.min_degree {
+            constraints.push(format!("min degree {}", d));
+        }
+        match self.girth {
+            Some(4) => constraints.push("triangle free".to_string()),
+            Some(g) => constraints.push(format!("girth >= {}", g)),
+            None    => ()
+        }
+        if let Some(q) = self.loop_p {
+            constraints.push(format!("loops w/ likelihood {}", q));
+        }
+        if self.digraph && self.oriented {
+            constraints.push("oriented".to_string());
+        }
+        if self.bernoulli {
+            constraints.push("independent edges".to_string());
+        }
+        match self.planted {
+            Some(Planted::IndependentSet(k)) => constraints.push(format!("planted independent set of {} vertices", k)),
+            Some(Planted::VertexCover(k))    => constraints.push(format!("planted vertex cover of {} vertices", k)),
+            Some(Planted::Cut(bias))         => constraints.push(format!("planted cut (bias {})", bias)),
+            None => ()
+        }
+        if !constraints.is_empty() {
+            write!(f, " {}", constraints.join(", "))?;
+        }
+        if let Some(seed) = self.seed {
+            write!(f, " w/ seed {}", seed)?;
+        }
+        Ok(())
+    }
+}
+
+/// What is known about a graph regardless of the model which produced it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphMeta {
+    /// Are the edges directed
+    digraph: bool,
+    /// May the graph have self loops
+    self_loops: bool,
+    /// Are the anti-parallel arcs (`u -> v` and `v -> u`) of a digraph
+    /// forbidden
+    oriented: bool,
+    /// Describes how the graph was produced (e.g. `Erdos-Renyi G(n, p)`)
+    provenance: String,
+    /// Are the vertices numbered from zero (rather than one) in the outputs
+    zero_indexed: bool
+}
+impl GraphMeta {
+    /// Describes an undirected graph without self loops
+    pub fn new(provenance: impl Into<String>) -> Self {
+        GraphMeta {digraph: false, self_loops: false, oriented: false, provenance: provenance.into(), zero_indexed: false}
+    }
+    pub fn digraph(self) -> Self {
+        GraphMeta{digraph: true, ..self}
+    }
+    pub fn with_self_loops(self) -> Self {
+        GraphMeta{self_loops: true, ..self}
+    }
+    /// Forbids the anti-parallel arcs of a digraph (which is then an
+    /// orientation of a simple graph)
+    pub fn oriented(self) -> Self {
+        GraphMeta{oriented: true, ..self}
+    }
+    /// Numbers the vertices from zero (rather than one) in the outputs which
+    /// have no fixed convention (e.g. snap, csv or json). The formats having
+    /// a convention of their own (e.g. dimacs, metis or lad) are unaffected.
+    pub fn zero_indexed(self) -> Self {
+        GraphMeta{zero_indexed: true, ..self}
+    }
+    /// Returns true iff the edges of the graph are directed
+    pub fn is_digraph(&self) -> bool {
+        self.digraph
+    }
+    /// Returns true iff the graph may have self loops
+    pub fn allows_self_loops(&self) -> bool {
+        self.self_loops
+    }
+    /// Returns true iff the graph may have both the arcs `u -> v` and
+    /// `v -> u` (which is only the case of the digraphs that are not
+    /// oriented)
+    pub fn allows_antiparallel_arcs(&self) -> bool {
+        self.digraph && !self.oriented
+    }
+    /// Returns the description of how the graph was produced
+    pub fn provenance(&self) -> &str {
+        &self.provenance
+    }
+    /// Returns the number of the first vertex in the outputs (0 or 1)
+    pub fn base(&self) -> isize {
+        if self.zero_indexed { 0 } else { 1 }
+    }
+}
+/// The provenance of an Erdos-Renyi graph is its model (and seed)
+impl From<ErModel> for GraphMeta {
+    fn from(model: ErModel) -> Self {
+        GraphMeta {
+            digraph   : model.digraph,
+            self_loops: model.self_loops,
+            oriented  : model.oriented,
+            provenance: format!("Erdos-Renyi {}", model),
+            zero_indexed: model.zero_indexed
+        }
+    }
+}
+
+/// Returns the number of edges of the full mesh having n vertices
+fn nb_possible_edges(n: usize, digraph: bool, self_loops: bool) -> u128 {
+    let sources = n as u128;
+    let dests   = if self_loops { n } else { n.saturating_sub(1) } as u128;
+
+    if digraph {
+        sources * dests
+    } else {
+        (sources * dests) / 2
+    }
+}
+
+/// The edges of a graph along with their weights. The hasher is not randomly
+/// keyed so that the edges of a (seeded) graph are always iterated in the
+/// same order.
+type EdgeMap<W = isize> = HashMap<Edge, W, BuildHasherDefault<DefaultHasher>>;
+
+/// The numeric edge weights: those which can be compared and converted to
+/// a float (e.g. to scale the width of the edges in the dot output)
+pub trait Weight: Copy + PartialOrd + fmt::Display {
+    /// Returns the weight as a float
+    fn to_f64(self) -> f64;
+}
+impl Weight for isize {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl Weight for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
 }
+
+/// Runs the given writer on an in-memory buffer and returns what it wrote
+fn written<F>(write: F) -> String
+    where F: FnOnce(&mut Vec<u8>) -> io::Result<()>
+{
+    let mut buffer = vec![];
+    write(&mut buffer).expect("writing to memory cannot fail");
+    String::from_utf8(buffer).expect("all writers produce utf8 text")
+}
+
 /// A vertex is basically just a typesafe integer id of the vertex
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Vertex {
     /// The vertex identifier
     id: isize
 }
+impl Vertex {
+    /// Creates the vertex having the given identifier (vertices are numbered
+    /// from 1)
+    pub fn new(id: isize) -> Self {
+        Vertex{id}
+    }
+    /// Returns the identifier of the vertex (numbered from 1)
+    pub fn id(self) -> isize {
+        self.id
+    }
+}
 
 /// An edge connects two vertices
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
@@ -89,6 +524,18 @@ pub struct Edge {
     dst   : Vertex,
 }
 impl Edge {
+    /// Creates the edge connecting `src` to `dst`
+    pub fn new(src: Vertex, dst: Vertex) -> Self {
+        Edge{src, dst}
+    }
+    /// Returns the source end of the edge
+    pub fn src(self) -> Vertex {
+        self.src
+    }
+    /// Returns the target end of the edge
+    pub fn dst(self) -> Vertex {
+        self.dst
+    }
     /// Returns true iff this edge is self looping
     pub fn is_self_loop(self) -> bool {
         self.src == self.dst
@@ -98,58 +545,117 @@ impl Edge {
     }
 }
 
-/// A graph as can be random generated
+/// A graph as can be random generated. Its edges weigh an `isize` by
+/// default, but any other weight type can be used (see `map_weights`).
 #[derive(Debug, Clone)]
-pub struct Graph {
-    model: ErModel,
+pub struct Graph<W = isize> {
+    meta : GraphMeta,
     n    : usize,
-    list : HashMap<Edge, isize>
+    list : EdgeMap<W>,
+    /// The adjacency index (built on demand by the neighbourhood queries)
+    adj  : OnceLock<adjacency::Adjacency>,
+    /// The (optional) labels, classes and weights of the vertices
+    labels: labels::VertexLabels,
+    /// The (optional) additional attributes of the edges
+    attrs: attributes::EdgeAttributes,
+    /// The structure planted in the graph when it was generated (if any)
+    certificate: Option<Certificate>
 }
 
-impl Graph {
-    pub fn pluck_random_weights(&mut self, from: &[isize]) {
-        let mut rng = thread_rng();
-        let dist= Uniform::new(0, from.len());
-
-        for (_e, w) in self.list.iter_mut() {
-            *w = from[dist.sample(&mut rng)];
+impl<W> Graph<W> {
+    /// Returns a graph having n vertices and no edge at all
+    fn empty(meta: GraphMeta, n: usize) -> Self {
+        Graph{meta, n, list: Default::default(), adj: OnceLock::new(), labels: Default::default(), attrs: Default::default(), certificate: None}
+    }
+    /// Returns what is known about the graph (directedness, self loops and
+    /// provenance)
+    pub fn meta(&self) -> &GraphMeta {
+        &self.meta
+    }
+    /// Numbers the vertices from zero (rather than one) in the outputs which
+    /// have no fixed convention. See `GraphMeta::zero_indexed`.
+    pub fn set_zero_indexed(&mut self, zero_indexed: bool) {
+        self.meta.zero_indexed = zero_indexed;
+    }
+    /// Returns the number of the vertex `v` in the outputs which follow the
+    /// indexing base of the graph
+    pub(crate) fn index(&self, v: Vertex) -> isize {
+        v.id - 1 + self.meta.base()
+    }
+    /// Returns the number of vertices in the graph
+    pub fn num_vertices(&self) -> usize {
+        self.n
+    }
+    /// Returns the number of edges in the graph
+    pub fn num_edges(&self) -> usize {
+        self.list.len()
+    }
+    /// Iterates over the vertices of the graph (in increasing id order)
+    pub fn vertices(&self) -> impl Iterator<Item=Vertex> {
+        (1..=self.n as isize).map(|id| Vertex{id})
+    }
+    /// Iterates over the edges of the graph (in no particular order)
+    pub fn edges(&self) -> impl Iterator<Item=Edge> + '_ {
+        self.list.keys().copied()
+    }
+    /// Returns the same graph where the weight of each edge is replaced by
+    /// the given function of that edge and its weight (e.g. to get floating
+    /// point or unit weights).
+    pub fn map_weights<U, F>(&self, mut f: F) -> Graph<U>
+        where F: FnMut(Edge, &W) -> U
+    {
+        Graph {
+            meta  : self.meta.clone(),
+            n     : self.n,
+            list  : self.list.iter().map(|(e, w)| (*e, f(*e, w))).collect(),
+            adj   : self.adj.clone(),
+            labels: self.labels.clone(),
+            attrs : self.attrs.clone(),
+            certificate: self.certificate.clone()
         }
     }
-    pub fn to_dimacs(&self) -> String {
-        let mut out = vec![];
+}
 
-        let gtype = if self.model.digraph    { "digraph" } else {"graph"};
-        let loops = if self.model.self_loops { "" }        else { " NOT"};
-        out.push(format!("c Pseudo-random Erdos-Renyi {} G({}, {})", gtype, self.model.n, self.model.p));
-        out.push(format!("c it was generated to{} allow self loops", loops));
-        out.push(format!("c This graph has {} vertices and {} edges", self.n, self.list.len()));
-        out.push("c -------------------------------------------------------------".to_string());
-        out.push("c Generated w/ graph_gen: https://github.com/xgillard/graph_gen".to_string());
+impl<W: Clone> Graph<W> {
+    /// Iterates over the edges of the graph along with their weights
+    pub fn weighted_edges(&self) -> impl Iterator<Item=(Edge, W)> + '_ {
+        self.list.iter().map(|(e, w)| (*e, w.clone()))
+    }
+    /// Returns the weight of the given edge (if it belongs to the graph). In
+    /// an undirected graph, the edge can be given in either orientation.
+    pub fn weight(&self, edge: Edge) -> Option<W> {
+        self.stored(edge).map(|e| self.list[&e].clone())
+    }
 
-        out.push(format!("{} {}", self.n, self.list.len()));
+    pub fn pluck_random_weights(&mut self, from: &[W]) {
+        let mut rng = crate::random::weight_rng();
+        let dist= Uniform::new(0, from.len());
 
-        for (edge, w) in self.list.iter() {
-            out.push(format!("{} {} {}", edge.src.id, edge.dst.id, w));
+        for (_e, w) in self.list.iter_mut() {
+            *w = from[dist.sample(&mut rng)].clone();
         }
+    }
+}
 
-        out.join("\n")
+impl<T: Weight> Graph<T> {
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
     }
 
-    pub fn to_dot(&self) -> String {
-        let mut out = vec![];
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, out: W) -> io::Result<()> {
+        self.write_dimacs_styled(out, &DimacsStyle::default())
+    }
+}
 
-        let gtype     = if self.model.digraph { "digraph" } else {"graph"};
-        let connector = if self.model.digraph { "->" }      else { "--" };
-        out.push(format!("{} g {{", gtype));
-        for v in 1..=self.n {
-            out.push(format!("  {};", v));
-        }
-        for (edge, w) in self.list.iter() {
-            out.push(format!("  {} {} {} [label={}];", edge.src.id, connector, edge.dst.id, w));
-        }
-        out.push("}".to_owned());
+impl<T: Weight> Graph<T> {
+    pub fn to_dot(&self) -> String {
+        crate::written(|out| self.write_dot(out))
+    }
 
-        out.join("\n")
+    /// Same as `to_dot` but streams the output to the given writer
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.write_dot_styled(out, &DotStyle::default())
     }
 }
 
@@ -157,16 +663,31 @@ impl Graph {
 pub struct WeightedMaxCliqueGraph {
     g: Graph,
     n: usize,
-    w: Vec<isize>
+    w: Vec<isize>,
+    /// The style of the dimacs output (`p edge` header and unweighted edges
+    /// by default)
+    style: DimacsStyle
 }
 impl WeightedMaxCliqueGraph {
+    /// The vertices of the instance weigh what they weigh in the graph (or 1
+    /// when the graph has no vertex weights)
     pub fn new(g: Graph) -> Self {
-        let n = g.n;
-        WeightedMaxCliqueGraph{g, n, w: vec![1; n]}
+        let n     = g.n;
+        let w     = g.vertex_weights().unwrap_or_else(|| vec![1; n]);
+        let style = DimacsStyle::new().header(DimacsHeader::Edge).weighted(false);
+        WeightedMaxCliqueGraph{g, n, w, style}
+    }
+    /// Writes the dimacs output with the given style
+    pub fn with_style(self, style: DimacsStyle) -> Self {
+        WeightedMaxCliqueGraph{style, ..self}
+    }
+    /// Returns the style of the dimacs output
+    pub fn style(&self) -> &DimacsStyle {
+        &self.style
     }
 
     pub fn pluck_random_weights(&mut self, from: &[isize]) {
-        let mut rng = thread_rng();
+        let mut rng = crate::random::weight_rng();
         let dist= Uniform::new(0, from.len());
 
         for w in self.w.iter_mut() {
@@ -177,30 +698,33 @@ impl WeightedMaxCliqueGraph {
     pub fn to_dot(&self) -> String {
         self.g.to_dot()
     }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
     pub fn to_dimacs(&self) -> String {
-        let mut out = vec![];
+        crate::written(|out| self.write_dimacs(out))
+    }
 
-        let gtype = if self.g.model.digraph    { "digraph" } else {"graph"};
-        let loops = if self.g.model.self_loops { "" }        else { " NOT"};
-        out.push(format!("c Pseudo-random Erdos-Renyi {} G({}, {})", gtype, self.g.model.n, self.g.model.p));
-        out.push(format!("c it was generated to{} allow self loops", loops));
-        out.push(format!("c This graph has {} vertices and {} edges", self.g.n, self.g.list.len()));
-        out.push("c -------------------------------------------------------------".to_string());
-        out.push("c Generated w/ graph_gen: https://github.com/xgillard/graph_gen".to_string());
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let gtype = if self.g.meta.digraph    { "digraph" } else {"graph"};
+        let loops = if self.g.meta.self_loops { "" }        else { " NOT"};
+        writeln!(out, "c Weighted max clique instance on a {} from {}", gtype, self.g.meta.provenance)?;
+        writeln!(out, "c it was generated to{} allow self loops", loops)?;
+        writeln!(out, "c This graph has {} vertices and {} edges", self.g.n, self.g.list.len())?;
+        self.g.write_certificate(&mut out, "c")?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
 
-        out.push(format!("p edge {} {}", self.g.n, self.g.list.len()));
+        self.style.write_header(&mut out, self.n, self.g.list.len())?;
 
-        out.push("c Vertices weights (defaults to 1)".to_string());
+        writeln!(out, "c Vertices weights (defaults to 1)")?;
         for (i, w) in self.w.iter().enumerate() {
-            out.push(format!("n {} {}", 1+i, w));
-        }
-
-        out.push("c Edges list".to_string());
-        for (edge, _w) in self.g.list.iter() {
-            out.push(format!("e {} {}", edge.src.id, edge.dst.id));
+            writeln!(out, "n {} {}", 1+i, w)?;
         }
 
-        out.join("\n")
+        writeln!(out, "c Edges list")?;
+        self.style.write_edges(&mut out, &self.g)
     }
 }
 
@@ -213,38 +737,44 @@ impl Max2SatGraph {
         Max2SatGraph{g}
     }
     pub fn to_dimacs(&self) -> String {
-        let mut out = vec![];
-
-        let loops = if self.g.model.self_loops { "" } else { " NOT"};
-        out.push(format!("c Pseudo-random max2sat instance generated w/ Erdos-Renyi G({}, {}) model", self.g.model.n, self.g.model.p));
-        out.push(format!("c it was generated to{} allow self loops", loops));
-        out.push(format!("c This instance has {} variables and {} clauses", self.g.n/2, self.g.list.len()));
-        out.push("c -------------------------------------------------------------".to_string());
-        out.push("c Each clause reads <weight> <source> <dest> 0".to_string());
-        out.push("c -------------------------------------------------------------".to_string());
-        out.push("c Generated w/ graph_gen: https://github.com/xgillard/graph_gen".to_string());
-        out.push(format!("p wcnf {} {}", self.g.n/2, self.g.list.len()));
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let loops = if self.g.meta.self_loops { "" } else { " NOT"};
+        writeln!(out, "c Max2sat instance on a graph from {}", self.g.meta.provenance)?;
+        writeln!(out, "c it was generated to{} allow self loops", loops)?;
+        writeln!(out, "c This instance has {} variables and {} clauses", self.g.n/2, self.g.list.len())?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Each clause reads <weight> <source> <dest> 0")?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p wcnf {} {}", self.g.n/2, self.g.list.len())?;
 
         for (edge, w) in self.g.list.iter() {
-            out.push(format!("{} {} {} 0", w, self.literal(edge.src), self.literal(edge.dst)));
+            writeln!(out, "{} {} {} 0", w, self.literal(edge.src), self.literal(edge.dst))?;
         }
 
-        out.join("\n")
+        Ok(())
     }
 
     pub fn to_dot(&self) -> String {
-        let mut out = vec![];
+        crate::written(|out| self.write_dot(out))
+    }
 
-        out.push("graph wcnf {".to_string());
+    /// Same as `to_dot` but streams the output to the given writer
+    pub fn write_dot<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "graph wcnf {{")?;
         for v in 1..=self.g.n/2 {
-            out.push(format!("  {};", v));
+            writeln!(out, "  {};", v)?;
         }
         for (edge, w) in self.g.list.iter() {
-            out.push(format!("  {} -- {} [label={}];", self.literal(edge.src), self.literal(edge.dst), w));
+            writeln!(out, "  {} -- {} [label={}];", self.literal(edge.src), self.literal(edge.dst), w)?;
         }
-        out.push("}".to_owned());
+        writeln!(out, "}}")?;
 
-        out.join("\n")
+        Ok(())
     }
 
     fn literal(&self, v: Vertex) -> isize {
@@ -260,41 +790,115 @@ impl Max2SatGraph {
 pub enum Generatable {
     GenGraph{g: Graph},
     ClqGraph {g: WeightedMaxCliqueGraph},
-    GenSat  {s: Max2SatGraph}
+    GenSat  {s: Max2SatGraph},
+    ColSat  {s: ColoringSatGraph},
+    CutQubo {q: MaxCutQubo},
+    IlpGraph{g: IlpGraph},
+    IsoPair {p: IsoPair},
+    SubIso  {p: SubIsoPair},
+    Atsp    {t: AtspInstance},
+    Cvrp    {c: CvrpInstance},
+    Matching{m: MatchingInstance},
+    MinCostFlow{f: MinCostFlowInstance},
+    Metis   {g: MetisGraph},
+    Chromatic{c: PlantedColoring},
+    CliqueCover{c: CliqueCoverInstance},
+    Feedback{f: PlantedFeedbackSet},
+    KTree   {t: PartialKTree}
 }
 impl Generatable {
     pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+    pub fn to_dot(&self) -> String {
+        crate::written(|out| self.write_dot(out))
+    }
+    /// Writes the instance in its 'natural' text format to the given writer
+    pub fn write_dimacs<W: Write>(&self, out: W) -> io::Result<()> {
         match self {
-            Generatable::GenGraph {g} => g.to_dimacs(),
-            Generatable::ClqGraph {g} => g.to_dimacs(),
-            Generatable::GenSat   {s} => s.to_dimacs()
+            Generatable::GenGraph {g}    => g.write_dimacs(out),
+            Generatable::ClqGraph {g}    => g.write_dimacs(out),
+            Generatable::GenSat   {s}    => s.write_dimacs(out),
+            Generatable::ColSat   {s}    => s.write_dimacs(out),
+            Generatable::CutQubo  {q}    => q.write_text(out),
+            Generatable::IlpGraph {g}    => g.write_lp(out),
+            Generatable::IsoPair  {p}    => p.write_dimacs(out),
+            Generatable::SubIso   {p}    => p.write_dimacs(out),
+            Generatable::Atsp     {t}    => t.write_tsplib(out),
+            Generatable::Cvrp     {c}    => c.write_cvrplib(out),
+            Generatable::Matching {m}    => m.write_text(out),
+            Generatable::MinCostFlow{f}  => f.write_dimacs(out),
+            Generatable::Metis    {g}    => g.write_metis(out),
+            Generatable::Chromatic{c}    => c.write_dimacs(out),
+            Generatable::CliqueCover{c}  => c.write_dimacs(out),
+            Generatable::Feedback {f}    => f.write_pace(out),
+            Generatable::KTree    {t}    => t.write_pace(out)
         }
     }
-    pub fn to_dot(&self) -> String {
+    /// Writes the graphviz representation of the instance to the given writer
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        match self {
+            Generatable::GenGraph {g}    => g.write_dot(out),
+            Generatable::ClqGraph {g}    => g.write_dot(out),
+            Generatable::GenSat   {s}    => s.write_dot(out),
+            Generatable::ColSat   {s}    => s.write_dot(out),
+            Generatable::CutQubo  {q}    => q.write_dot(out),
+            Generatable::IlpGraph {g}    => g.write_dot(out),
+            Generatable::IsoPair  {p}    => p.write_dot(out),
+            Generatable::SubIso   {p}    => p.write_dot(out),
+            Generatable::Atsp     {t}    => t.write_dot(out),
+            Generatable::Cvrp     {c}    => c.write_dot(out),
+            Generatable::Matching {m}    => m.write_dot(out),
+            Generatable::MinCostFlow{f}  => f.write_dot(out),
+            Generatable::Metis    {g}    => g.write_dot(out),
+            Generatable::Chromatic{c}    => c.write_dot(out),
+            Generatable::CliqueCover{c}  => c.write_dot(out),
+            Generatable::Feedback {f}    => f.write_dot(out),
+            Generatable::KTree    {t}    => t.write_dot(out)
+        }
+    }
+    /// Returns the graph underlying the instance (if there is one). This is
+    /// the graph which gets written by the general purpose graph formats.
+    pub fn graph(&self) -> Option<&Graph> {
         match self {
-            Generatable::GenGraph {g} => g.to_dot(),
-            Generatable::ClqGraph {g} => g.to_dot(),
-            Generatable::GenSat   {s} => s.to_dot()
+            Generatable::GenGraph {g}    => Some(g),
+            Generatable::ClqGraph {g}    => Some(&g.g),
+            Generatable::GenSat   {s}    => Some(&s.g),
+            Generatable::ColSat   {s}    => Some(s.graph()),
+            Generatable::CutQubo  {q}    => Some(q.graph()),
+            Generatable::IlpGraph {g}    => Some(g.graph()),
+            Generatable::IsoPair  {p}    => Some(p.first()),
+            Generatable::SubIso   {p}    => Some(p.target()),
+            Generatable::Atsp     {..}   => None,
+            Generatable::Cvrp     {..}   => None,
+            Generatable::Matching {m}    => Some(m.graph()),
+            Generatable::MinCostFlow{f}  => Some(f.graph()),
+            Generatable::Metis    {g}    => Some(g.graph()),
+            Generatable::Chromatic{c}    => Some(c.graph()),
+            Generatable::CliqueCover{c}  => Some(c.graph()),
+            Generatable::Feedback {f}    => Some(f.graph()),
+            Generatable::KTree    {t}    => Some(t.graph())
         }
     }
 }
 
-/// The graph generator using ER model
+/// The graph generator using ER model. It draws its random numbers from
+/// any `Rng` (a `StdRng` by default).
 #[derive(Debug)]
-pub struct ErGenerator {
+pub struct ErGenerator<R: Rng = StdRng> {
     /// The er model
     model: ErModel,
     /// The random number generator
-    rng  : ThreadRng,
+    rng  : R,
     /// Uniform distribution to pick numbers from
     dist : Uniform<u128>
 }
 
-impl ErGenerator {
-    fn new(model: ErModel) -> ErGenerator {
+impl<R: Rng> ErGenerator<R> {
+    fn new(model: ErModel, rng: R) -> ErGenerator<R> {
         ErGenerator {
             model,
-            rng  : thread_rng(),
+            rng,
             dist : Uniform::new(0, model.n as u128 * model.n as u128)
         }
     }
@@ -313,30 +917,122 @@ impl ErGenerator {
         Edge { src: Vertex{id: src + 1}, dst: Vertex{id: dst + 1} }
     }
 
+    /// Generates a random graph which satisfies all the constraints of the
+    /// model
     pub fn gen(&mut self) -> Graph {
-        let mut g = Graph{model: self.model, n: self.model.n, list: Default::default()};
+        loop {
+            let g = self.sample();
+            if self.model.connectivity != Some(Connectivity::Resample) || g.is_connected() {
+                return g;
+            }
+        }
+    }
+
+    /// Samples a random graph (with geometric skipping when it is a sparse
+    /// G(n, p) graph, see `ErModel::bernoulli`, and without any duplicate
+    /// check when it is a dense graph without constraints, see
+    /// `sample_dense`). Otherwise, the planted structures (if any) come
+    /// first, then the loops picked on their own, the structures which
+    /// connect the graph and the edges which top up the vertices to the
+    /// minimum degree. The random edges are picked next, and the isolated
+    /// vertices are attached last.
+    fn sample(&mut self) -> Graph {
+        if self.model.skips() {
+            return self.sample_sparse();
+        }
+        if self.model.is_dense() {
+            return self.sample_dense();
+        }
+        let nb_edges = self.draw_nb_edges();
+        let mut s    = Sample::new(self.model, nb_edges);
+
+        if let Some(planted) = self.model.planted {
+            self.plant(&mut s, planted);
+        }
+
+        let nb_edges = nb_edges + self.plant_loops(&mut s);
+
+        match self.model.connectivity {
+            Some(Connectivity::SpanningTree)     => self.plant_spanning_tree(&mut s),
+            Some(Connectivity::HamiltonianCycle) => self.plant_hamiltonian_cycle(&mut s),
+            _ => ()
+        }
 
-        let nb_edges = self.model.nb_edges_to_pick();
-        g.list.reserve(nb_edges);
+        if let Some(d) = self.model.min_degree {
+            self.top_up_degrees(&mut s, d);
+        }
 
-        while g.list.len() < nb_edges {
+        // the constraints can prevent the graph from ever getting all of its
+        // edges: every now and then, check that it still can grow
+        let stuck_after = self.model.n.saturating_mul(self.model.n).max(1024);
+        let mut rejected = 0;
+        while s.len() < nb_edges {
             let edge = self.next_edge();
 
-            if edge.is_self_loop() && !self.model.self_loops {
-                continue;
+            if self.accepts(&s, edge) && self.keeps(&s, edge) {
+                s.insert(edge);
+                rejected = 0;
+            } else {
+                rejected += 1;
+                if rejected >= stuck_after {
+                    if !self.can_grow(&s) {
+                        break;
+                    }
+                    rejected = 0;
+                }
             }
+        }
 
-            if g.list.contains_key(&edge) || g.list.contains_key(&edge.rev()) {
-                continue;
-            }
+        if self.model.no_isolated {
+            self.attach_isolated_vertices(&mut s);
+        }
 
-            g.list.insert(edge, 1);
+        s.graph
+    }
+
+    /// Picks the self loops on their own (when they have a likelihood of
+    /// their own), and returns how many of them were added
+    fn plant_loops(&mut self, s: &mut Sample) -> usize {
+        let mut added = 0;
+        let nb_loops  = self.draw_nb_loops();
+        for i in index::sample(&mut self.rng, self.model.n, nb_loops).into_iter() {
+            let v = Vertex{id: i as isize + 1};
+            if self.fits(s, Edge{src: v, dst: v}) {
+                s.insert(Edge{src: v, dst: v});
+                added += 1;
+            }
         }
+        added
+    }
 
-        g
+    /// Returns true iff the (randomly picked) edge can be added to the graph
+    /// being sampled
+    fn accepts(&self, s: &Sample, edge: Edge) -> bool {
+        if edge.is_self_loop() && !self.model.picks_loops() {
+            return false;
+        }
+        self.fits(s, edge)
+    }
+    /// Returns true iff the edge can be added to the graph being sampled
+    /// without breaking the constraints of the model
+    fn fits(&self, s: &Sample, edge: Edge) -> bool {
+        if s.contains(edge) {
+            return false;
+        }
+        if !s.may_join(edge.src, edge.dst) {
+            return false;
+        }
+        if self.model.girth.is_some_and(|g| s.closes_short_cycle(edge, g)) {
+            return false;
+        }
+        match self.model.max_degree {
+            Some(d) if edge.is_self_loop() => s.degree(edge.src) + 2 <= d,
+            Some(d) => s.degree(edge.src) < d && s.degree(edge.dst) < d,
+            None    => true
+        }
     }
 }
-impl Iterator for ErGenerator {
+impl<R: Rng> Iterator for ErGenerator<R> {
     type Item = Graph;
     fn next(&mut self) -> Option<Graph> {
         Some(self.gen())
@@ -0,0 +1,212 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes the integer linear programming formulations of some
+//! standard graph problems in the CPLEX LP file format. This way, MIP solvers
+//! can be benchmarked on the random graphs without an extra modelling step.
+
+use crate::Graph;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use std::io::{self, Write};
+
+/// The graph problems whose ILP formulation can be written
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum IlpProblem {
+    /// Maximum clique: max sum x_v s.t. x_u + x_v <= 1 for all non edges
+    Clique,
+    /// Minimum vertex cover: min sum x_v s.t. x_u + x_v >= 1 for all edges
+    VertexCover,
+    /// Maximum cut: max sum w_uv y_uv s.t. y_uv <= x_u + x_v and
+    /// y_uv <= 2 - x_u - x_v for all edges
+    MaxCut
+}
+impl FromStr for IlpProblem {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<IlpProblem, String> {
+        match txt.to_lowercase().as_str() {
+            "clique"                => Ok(IlpProblem::Clique),
+            "cover" | "vertexcover" => Ok(IlpProblem::VertexCover),
+            "cut"   | "maxcut"      => Ok(IlpProblem::MaxCut),
+            _                       => Err(txt.to_owned())
+        }
+    }
+}
+
+/// The ILP formulation of some graph problem over a random graph
+#[derive(Debug, Clone)]
+pub struct IlpGraph {
+    g      : Graph,
+    problem: IlpProblem
+}
+impl IlpGraph {
+    pub fn new(g: Graph, problem: IlpProblem) -> Self {
+        IlpGraph{g, problem}
+    }
+
+    /// Returns the graph underlying this instance
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+
+    /// Returns the set of (undirected) pairs of distinct adjacent vertices
+    fn adjacent_pairs(&self) -> BTreeSet<(isize, isize)> {
+        self.g.list.keys()
+            .filter(|e| !e.is_self_loop())
+            .map(|e| (e.src.id.min(e.dst.id), e.src.id.max(e.dst.id)))
+            .collect()
+    }
+
+    pub fn to_lp(&self) -> String {
+        crate::written(|out| self.write_lp(out))
+    }
+
+    /// Same as `to_lp` but streams the output to the given writer
+    pub fn write_lp<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let gtype = if self.g.meta.digraph { "digraph" } else {"graph"};
+        let descr = match self.problem {
+            IlpProblem::Clique      => "maximum clique",
+            IlpProblem::VertexCover => "minimum vertex cover",
+            IlpProblem::MaxCut      => "maximum cut"
+        };
+        writeln!(out, "\\ Pseudo-random {} instance on a {} from {}", descr, gtype, self.g.meta.provenance)?;
+        writeln!(out, "\\ The graph has {} vertices and {} edges", self.g.n, self.g.list.len())?;
+        writeln!(out, "\\ -------------------------------------------------------------")?;
+        writeln!(out, "\\ Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+
+        match self.problem {
+            IlpProblem::Clique      => self.clique(&mut out)?,
+            IlpProblem::VertexCover => self.vertex_cover(&mut out)?,
+            IlpProblem::MaxCut      => self.max_cut(&mut out)?,
+        }
+
+        writeln!(out, "End")?;
+        Ok(())
+    }
+
+    fn clique<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let edges = self.adjacent_pairs();
+        let n     = self.g.n as isize;
+
+        writeln!(out, "Maximize")?;
+        Self::linear(out, " obj:", (1..=n).map(|v| (1, format!("x{}", v))))?;
+        writeln!(out, "Subject To")?;
+        for u in 1..=n {
+            for v in u+1..=n {
+                if !edges.contains(&(u, v)) {
+                    writeln!(out, " ne_{}_{}: x{} + x{} <= 1", u, v, u, v)?;
+                }
+            }
+        }
+        Self::binaries(out, (1..=n).map(|v| format!("x{}", v)))
+    }
+
+    fn vertex_cover<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let n = self.g.n as isize;
+
+        writeln!(out, "Minimize")?;
+        Self::linear(out, " obj:", (1..=n).map(|v| (1, format!("x{}", v))))?;
+        writeln!(out, "Subject To")?;
+        let loops = self.g.list.keys()
+            .filter(|e| e.is_self_loop())
+            .map(|e| e.src.id)
+            .collect::<BTreeSet<isize>>();
+        for v in loops {
+            writeln!(out, " loop_{}: x{} >= 1", v, v)?;
+        }
+        for (u, v) in self.adjacent_pairs() {
+            writeln!(out, " e_{}_{}: x{} + x{} >= 1", u, v, u, v)?;
+        }
+        Self::binaries(out, (1..=n).map(|v| format!("x{}", v)))
+    }
+
+    fn max_cut<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let n = self.g.n as isize;
+
+        let mut weights = BTreeMap::new();
+        for (e, w) in self.g.list.iter().filter(|(e, _)| !e.is_self_loop()) {
+            let key = (e.src.id.min(e.dst.id), e.src.id.max(e.dst.id));
+            *weights.entry(key).or_insert(0) += *w;
+        }
+
+        writeln!(out, "Maximize")?;
+        Self::linear(out, " obj:", weights.iter().map(|((u, v), w)| (*w, format!("y{}_{}", u, v))))?;
+        writeln!(out, "Subject To")?;
+        for ((u, v), w) in weights.iter() {
+            writeln!(out, " a_{}_{}: y{}_{} - x{} - x{} <= 0", u, v, u, v, u, v)?;
+            writeln!(out, " b_{}_{}: y{}_{} + x{} + x{} <= 2", u, v, u, v, u, v)?;
+            // negative weights give an incentive to leave y_uv at zero even
+            // when the edge is cut: this must be forbidden too.
+            if *w < 0 {
+                writeln!(out, " c_{}_{}: y{}_{} - x{} + x{} >= 0", u, v, u, v, u, v)?;
+                writeln!(out, " d_{}_{}: y{}_{} + x{} - x{} >= 0", u, v, u, v, u, v)?;
+            }
+        }
+        let xs = (1..=n).map(|v| format!("x{}", v));
+        let ys = weights.keys().map(|(u, v)| format!("y{}_{}", u, v));
+        Self::binaries(out, xs.chain(ys))
+    }
+
+    /// Writes a linear expression, wrapping it over several lines so as to
+    /// keep the lines short enough for the LP readers. The expression of an
+    /// objective without any term is `0 x1`.
+    fn linear<W, I>(out: &mut W, name: &str, terms: I) -> io::Result<()>
+        where W: Write, I: Iterator<Item=(isize, String)>
+    {
+        let mut line = name.to_string();
+        let mut empty= true;
+        for (i, (coef, var)) in terms.enumerate() {
+            if i > 0 && i % 10 == 0 {
+                writeln!(out, "{}", line)?;
+                line = String::new();
+            }
+            let sign = if coef < 0 { " -" } else if empty { "" } else { " +" };
+            if coef.abs() == 1 {
+                line.push_str(&format!("{} {}", sign, var));
+            } else {
+                line.push_str(&format!("{} {} {}", sign, coef.abs(), var));
+            }
+            empty = false;
+        }
+        if empty {
+            line.push_str(" 0 x1");
+        }
+        writeln!(out, "{}", line)
+    }
+
+    fn binaries<W, I>(out: &mut W, vars: I) -> io::Result<()>
+        where W: Write, I: Iterator<Item=String>
+    {
+        writeln!(out, "Binary")?;
+        let vars = vars.collect::<Vec<String>>();
+        for chunk in vars.chunks(10) {
+            writeln!(out, " {}", chunk.join(" "))?;
+        }
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
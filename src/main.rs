@@ -17,45 +17,487 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use graph_gen::{ErModel, Graph, Max2SatGraph, Generatable, WeightedMaxCliqueGraph};
+use graph_gen::{Connectivity, ErGenerator, ErModel, Graph, GraphGenerator, Max2SatGraph, Generatable, WeightedMaxCliqueGraph, ColoringSatGraph, MaxCutQubo, IlpGraph, IlpProblem, IsoPair, SubIsoPair, AtspInstance, CvrpInstance, MatchingInstance, MinCostFlowInstance, MetisGraph, DdoPreset, PlantedColoring, CliqueCoverInstance, PlantedFeedbackSet, PartialKTree, DotStyle, DimacsHeader, Point, Weight, DimacsStyle, MergePolicy, WeightDistribution, gzipped};
+use rand::{thread_rng, Rng};
 use structopt::StructOpt;
-use crate::Output::Dimacs;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::ops::Neg;
+use std::process;
 use std::str::FromStr;
 
 /// Convenience tool to generate pseudo random graphs.
 #[derive(StructOpt)]
 struct Args {
     /// The number of vertices in the generated graph
-    #[structopt(name="nb_vertices", short, long)]
-    n: usize,
-    /// The likelihood of any edge to be picked
-    #[structopt(name="probability", short, long)]
-    p: f64,
+    #[structopt(name="nb_vertices", short, long, required_unless="input")]
+    n: Option<usize>,
+    /// The likelihood of any edge to be picked (ignored by the atsp and cvrp
+    /// instances)
+    #[structopt(name="probability", short, long, required_unless_one=&["input", "atsp", "cvrp"])]
+    p: Option<f64>,
+    /// Reads the graph from the given (dimacs) file rather than generating
+    /// it. The `-d` flag tells whether it is a digraph
+    #[structopt(name="input", short, long)]
+    input: Option<String>,
+    /// If set, the input file is a plain edge list (`<src> <dst> [weight]`
+    /// lines separated by spaces, tabs or commas) rather than a dimacs file
+    #[structopt(name="edge_list", long, requires="input")]
+    edge_list: bool,
+    /// If set, the (generated or input) graph is replaced by its complement
+    #[structopt(name="complement", long)]
+    complement: bool,
+    /// If set, the arcs of the (generated or input) digraph are reversed
+    #[structopt(name="reverse", long)]
+    reverse: bool,
+    /// If set, the (generated or input) graph is replaced by its line graph
+    #[structopt(name="line_graph", long)]
+    line_graph: bool,
+    /// The number of random edges to contract in the (generated or input)
+    /// graph in order to get a random minor of it
+    #[structopt(name="contractions", long, default_value="0")]
+    contractions: usize,
+    /// The number of random edges to delete (after the contractions) in the
+    /// (generated or input) graph in order to get a random minor of it
+    #[structopt(name="deletions", long, default_value="0")]
+    deletions: usize,
+    /// If set, the (generated or input) graph is pruned down to its k-core
+    /// (the maximal subgraph where each vertex has a degree of at least k)
+    #[structopt(name="core", long)]
+    core: Option<usize>,
+    /// If set, the (generated or input) graph is reduced to its largest
+    /// connected component
+    #[structopt(name="largest_component", long)]
+    largest_component: bool,
+    /// If set, a report of the statistics of the graph (its summary
+    /// statistics, its connected components, whether it is connected,
+    /// bipartite and acyclic, its diameter, its number of
+    /// triangles, its clustering coefficients, its degree assortativity and
+    /// its degree histogram) is written on the standard error once the
+    /// instance has been written
+    #[structopt(name="stats", long)]
+    stats: bool,
+    /// If set, a single line summing up the graph (number of vertices and
+    /// edges, density, degree moments, number of loops and of components) is
+    /// written on the standard error once the instance has been written
+    #[structopt(name="summary", long, conflicts_with="stats")]
+    summary: bool,
+    /// If set, the statistics of the graph are also written (along with its
+    /// model, parameters and seed) as a JSON document to <prefix>.stats.json
+    /// so that the instance describes itself
+    #[structopt(name="stats_json", long, requires="prefix")]
+    stats_json: bool,
+    /// If set, the edge count, the degree distribution and the clustering of
+    /// the generated graph are compared with their expectation under its
+    /// Erdos-Renyi model, and the deviations are reported on the standard
+    /// error (a statistical self-test of the generator). This is only
+    /// meaningful for the graphs sampled from the model: neither input
+    /// graphs, nor transformed graphs, nor the instances of the generators
+    /// which are not Erdos-Renyi ones can be checked
+    #[structopt(name="check_model", long, conflicts_with_all=&[
+        "input", "complement", "line_graph", "contractions", "deletions", "core", "largest_component", "simplify",
+        "undirected", "orient", "rewire", "atsp", "cvrp", "matching", "chromatic", "clique_cover", "feedback",
+        "treewidth", "ddo_misp", "ddo_mcp", "ddo_max2sat"])]
+    check_model: bool,
+    /// If set, the vertices of the (generated or input) graph are renumbered
+    /// after a random permutation
+    #[structopt(name="shuffle", long)]
+    shuffle: bool,
+    /// If set, the self loops of the (generated or input) graph are removed
+    /// and the edges joining the same vertices (e.g. anti-parallel arcs) are
+    /// merged into one whose weight is the `min`, `max` or `sum` of theirs
+    #[structopt(name="simplify", long)]
+    simplify: Option<MergePolicy>,
+    /// If set, the (generated or input) digraph is turned into an undirected
+    /// graph. The weight of an edge coming from two anti-parallel arcs is the
+    /// `min`, `max` or `sum` of theirs
+    #[structopt(name="undirected", long)]
+    undirected: Option<MergePolicy>,
+    /// If set, the edges of the (generated or input) undirected graph are
+    /// oriented so as to make it a digraph: either at `random` or so that
+    /// the digraph is `acyclic`
+    #[structopt(name="orient", long)]
+    orient: Option<Orientation>,
+    /// If set, the given fraction (in [0, 1]) of the edges of the (generated
+    /// or input) graph is randomly rewired while preserving its degree
+    /// sequence
+    #[structopt(name="rewire", long)]
+    rewire: Option<f64>,
     /// If set, self loops are allowed in the generated graph
     #[structopt(name="loops", short, long)]
     loops: bool,
+    /// If set, self loops are allowed in the generated graph but have this
+    /// likelihood (rather than p): about q n random vertices get a loop
+    #[structopt(name="loop_probability", long, alias="loop-probability")]
+    loop_probability: Option<f64>,
+    /// If set, the generated graph is connected (weakly for a digraph):
+    /// either the graphs are sampled again until one is connected
+    /// (`resample`) or a random spanning tree is planted first (`tree`).
+    /// Planting a random Hamiltonian cycle first (`cycle`) makes digraphs
+    /// strongly connected.
+    #[structopt(name="connected", long)]
+    connected: Option<Connectivity>,
+    /// If set, an edge towards a random vertex is attached to each vertex of
+    /// the generated graph which would otherwise be isolated
+    #[structopt(name="no_isolated", long, alias="no-isolated")]
+    no_isolated: bool,
+    /// If set, the vertices of the generated graph have at most this degree
+    /// (in and out arcs count for digraphs). The graph has fewer edges than
+    /// expected when no edge can be added anymore.
+    #[structopt(name="max_degree", long, alias="max-degree")]
+    max_degree: Option<usize>,
+    /// If set, the vertices of the generated graph have at least this degree
+    /// (in and out arcs count for digraphs): they are topped up with random
+    /// edges before the other ones are picked
+    #[structopt(name="min_degree", long, alias="min-degree")]
+    min_degree: Option<usize>,
+    /// If set, the generated graph is triangle-free: the edges which would
+    /// close a triangle are rejected (the graph has fewer edges than expected
+    /// when no edge can be added anymore)
+    #[structopt(name="triangle_free", long, alias="triangle-free")]
+    triangle_free: bool,
+    /// If set, the generated graph has no cycle shorter than this: the edges
+    /// which would close one are rejected (the graph has fewer edges than
+    /// expected when no edge can be added anymore)
+    #[structopt(name="girth", long)]
+    girth: Option<usize>,
+    /// If set, this number of random vertices is reserved as an independent
+    /// set of the generated graph (no edge joins two of them). The set is
+    /// written as a certificate in the dimacs outputs.
+    #[structopt(name="independent_set", long, alias="independent-set")]
+    independent_set: Option<usize>,
+    /// If set, this number of random vertices is reserved as a vertex cover
+    /// of the generated graph (each edge touches one of them). The cover is
+    /// written as a certificate in the dimacs outputs.
+    #[structopt(name="vertex_cover", long, alias="vertex-cover", conflicts_with="independent_set")]
+    vertex_cover: Option<usize>,
+    /// If set, the vertices of the generated graph are split in two random
+    /// halves and the edges inside a half are rejected with this probability
+    /// (the bias). The weights of the edges crossing the halves are made
+    /// positive and the others negative, so that the planted cut is a maximum
+    /// cut: its value is written in the dimacs, qubo and ising outputs.
+    #[structopt(name="planted_cut", long, alias="planted-cut", conflicts_with_all=&["independent_set", "vertex_cover", "negative"])]
+    planted_cut: Option<f64>,
     /// If set, the generated graph will be a digraph
     #[structopt(name="digraph", short, long)]
     digraph: bool,
+    /// If set, the generated digraph has no anti-parallel arcs (it is an
+    /// orientation of a simple graph)
+    #[structopt(name="oriented", long, requires="digraph")]
+    oriented: bool,
+    /// If set, each candidate edge is included independently with the given
+    /// likelihood (classic G(n, p)) so that the number of edges varies from
+    /// one graph to the next, rather than being fixed to round(p N)
+    #[structopt(name="bernoulli", long)]
+    bernoulli: bool,
+    /// If set, the G(n, p) graph is sampled on all cores (its edges are
+    /// still entirely determined by the seed)
+    #[cfg(feature = "rayon")]
+    #[structopt(name="parallel", long, requires="bernoulli")]
+    parallel: bool,
     /// If set, the generated graph will be a max2sat instance
     #[structopt(name="max2sat", short, long)]
     max2sat: bool,
     /// If set, the generated graph will be a misp/maxclique instance
-    #[structopt(name="misp", short, long)]
+    #[structopt(name="misp", long)]
     misp: bool,
+    /// If set, the generated graph will be a k-colorability (cnf) instance
+    #[structopt(name="colors", short, long)]
+    coloring: Option<usize>,
+    /// If set, the generated graph will be a max-cut instance in qubo form
+    #[structopt(name="qubo", long)]
+    qubo: bool,
+    /// If set, the generated graph will be a max-cut instance in ising form
+    #[structopt(name="ising", long)]
+    ising: bool,
+    /// If set, the ILP formulation of the given problem (clique, cover or
+    /// maxcut) is written in the CPLEX LP format
+    #[structopt(name="lp", long)]
+    lp: Option<IlpProblem>,
+    /// If set, the output is a pair of isomorphic graphs along with the
+    /// mapping between them
+    #[structopt(name="iso", long)]
+    iso: bool,
+    /// The number of edges to perturb in the second graph of an iso pair
+    #[structopt(name="perturbations", long, default_value="0")]
+    perturbations: usize,
+    /// If set, the output is a subgraph isomorphism instance whose pattern
+    /// is a connected subgraph with the given number of vertices
+    #[structopt(name="subiso", long)]
+    subiso: Option<usize>,
+    /// When set, the pattern, target and embedding of a subgraph isomorphism
+    /// instance are also written to <prefix>.pattern.lad, <prefix>.target.lad
+    /// and <prefix>.embedding. This is also the path (without extension) of
+    /// the files written when several formats are asked (see `formats`)
+    #[structopt(name="prefix", long)]
+    prefix: Option<String>,
+    /// If set, the output is an asymmetric TSP instance (complete digraph)
+    /// in TSPLIB format. The probability is ignored in that case, and the
+    /// arc costs can only be picked among the weight candidates
+    #[structopt(name="atsp", long, conflicts_with_all=&[
+        "weight_dist", "degree_weights", "negative", "euclidean", "shuffle_weights", "rescale"])]
+    atsp: bool,
+    /// The maximum cost of an arc in the generated (A)TSP instances
+    #[structopt(name="max_cost", long, default_value="100")]
+    max_cost: isize,
+    /// If set, the output is a CVRP instance (in CVRPLIB format) with n
+    /// customers. The probability is ignored in that case
+    #[structopt(name="cvrp", long)]
+    cvrp: bool,
+    /// The capacity of the vehicles in the generated CVRP instances
+    #[structopt(name="capacity", long, default_value="100")]
+    capacity: usize,
+    /// The maximum demand of a customer in the generated CVRP instances
+    #[structopt(name="max_demand", long, default_value="30")]
+    max_demand: usize,
+    /// The side of the square where the points are scattered
+    #[structopt(name="side", long, default_value="1000")]
+    side: usize,
+    /// If set, the output is a maximum weighted matching instance
+    #[structopt(name="matching", long)]
+    matching: bool,
+    /// If set, the matching instance is bipartite with n vertices per side
+    #[structopt(name="bipartite", long)]
+    bipartite: bool,
+    /// If set, a perfect matching of known (optimal) weight is planted in
+    /// the matching instance
+    #[structopt(name="plant", long)]
+    plant: bool,
+    /// If set, the matching instance is written in the Blossom V format
+    #[structopt(name="blossom", long)]
+    blossom: bool,
+    /// If set, the output is a partitioning instance in METIS format. Its
+    /// edges are weighted when weight candidates are given
+    #[structopt(name="metis", long)]
+    metis: bool,
+    /// If set, the output is a min cost flow instance (in DIMACS format) from
+    /// the first to the last vertex, whose supply is the maximum flow
+    #[structopt(name="mcf", long)]
+    mcf: bool,
+    /// The distribution the arc capacities of the min cost flow instances
+    /// are drawn from (see `weight_dist`)
+    #[structopt(name="capacities", long, default_value="uniform:1:100")]
+    capacities: WeightDistribution,
+    /// The distribution the arc costs of the min cost flow instances are
+    /// drawn from (see `weight_dist`)
+    #[structopt(name="costs", long, default_value="uniform:1:100")]
+    costs: WeightDistribution,
+    /// Optional vertex weight candidates. The METIS, weighted clique (unless
+    /// `-w` is given), json and gml outputs write the vertex weights
+    #[structopt(name="vertex_weights", long)]
+    vertex_weights: Option<Vec<isize>>,
+    /// Optional distribution the vertex weights are drawn from (see
+    /// `weight_dist`)
+    #[structopt(name="vertex_weight_dist", long, alias="vertex-weight-dist", conflicts_with="vertex_weights")]
+    vertex_weight_dist: Option<WeightDistribution>,
+    /// If set, the output is a misp instance for the ddo example solver
+    #[structopt(name="ddo_misp", long)]
+    ddo_misp: bool,
+    /// If set, the output is a mcp instance for the ddo example solver
+    #[structopt(name="ddo_mcp", long)]
+    ddo_mcp: bool,
+    /// If set, the output is a max2sat instance for the ddo example solver
+    /// (with n variables)
+    #[structopt(name="ddo_max2sat", long)]
+    ddo_max2sat: bool,
+    /// If set, the output is a coloring benchmark whose chromatic number is
+    /// the given value (by construction)
+    #[structopt(name="chromatic", long)]
+    chromatic: Option<usize>,
+    /// If set, the output is a clique cover instance made of the union of
+    /// the given number of cliques (the probability is then the likelihood
+    /// of a vertex to join any additional clique)
+    #[structopt(name="clique_cover", long)]
+    clique_cover: Option<usize>,
+    /// If set, the output is a PACE 2022 directed feedback vertex set
+    /// instance whose minimum feedback vertex (and arc) set has the given
+    /// size (by construction)
+    #[structopt(name="feedback", long)]
+    feedback: Option<usize>,
+    /// If set, the output is a PACE treewidth instance: a partial k-tree
+    /// whose treewidth is at most the given value (by construction). The
+    /// probability is the likelihood of any edge of the k-tree to be kept
+    #[structopt(name="treewidth", long)]
+    treewidth: Option<usize>,
     /// The output language (defaults to dimacs)
     #[structopt(name="output", short, long)]
     output : Option<Output>,
+    /// Writes the instance in each of the given (comma separated) output
+    /// languages to <prefix>.<extension> rather than on the standard output
+    #[structopt(name="formats", long, use_delimiter=true, requires="prefix", conflicts_with="output")]
+    formats: Vec<Output>,
     /// Optional weight candidates
     #[structopt(name="weights", short, long)]
-    weights: Option<Vec<isize>>
+    weights: Option<Vec<isize>>,
+    /// Optional distribution the edge weights are drawn from (rather than
+    /// being picked among candidates): `uniform:<lo>:<hi>`, `constant:<w>`,
+    /// `discrete:<w1>=<p1>,<w2>=<p2>,..`, `gaussian:<mean>:<std>[:<lo>:<hi>]`
+    /// (truncated to lo..=hi if given), `exponential:<rate>` or
+    /// `pareto:<alpha>[:<scale>]`
+    #[structopt(name="weight_dist", long, alias="weight-dist", conflicts_with="weights")]
+    weight_dist: Option<WeightDistribution>,
+    /// If set, each edge `u -- v` weighs `scale * (deg(u) + deg(v))` plus a
+    /// noise drawn from the weight distribution (if one is given)
+    #[structopt(name="degree_weights", long, conflicts_with_all=&["weights", "euclidean"])]
+    degree_weights: Option<f64>,
+    /// If set, each edge weight is negated with the given probability
+    #[structopt(name="negative", long)]
+    negative: Option<f64>,
+    /// If set, the negated weights never close a negative cycle in the
+    /// digraph
+    #[structopt(name="no_negative_cycle", long, requires="negative")]
+    no_negative_cycle: bool,
+    /// If set, the vertices are randomly embedded in the `side x side`
+    /// square and each edge weighs the euclidean distance between its ends
+    #[structopt(name="euclidean", long, conflicts_with_all=&["weights", "weight_dist"])]
+    euclidean: Option<usize>,
+    /// If set, the (picked) edge weights are randomly permuted among the
+    /// edges
+    #[structopt(name="shuffle_weights", long)]
+    shuffle_weights: bool,
+    /// If set, the (picked) edge weights are linearly rescaled into the
+    /// given `lo:hi` range
+    #[structopt(name="rescale", long)]
+    rescale: Option<Bounds>,
+    /// If set, the edges of the (plain) graph get real weights: those drawn
+    /// from the weight distribution are not rounded anymore, and the
+    /// rescaled weights are not rounded either
+    #[structopt(name="real_weights", long, alias="real-weights", conflicts_with_all=&[
+        "max2sat", "misp", "colors", "qubo", "ising", "lp", "iso", "subiso", "atsp", "cvrp", "matching",
+        "metis", "mcf", "ddo_misp", "ddo_mcp", "ddo_max2sat", "chromatic", "clique_cover", "feedback", "treewidth"])]
+    real_weights: bool,
+    /// If set, the csv output has no header line
+    #[structopt(name="no_header", long)]
+    no_header: bool,
+    /// If set, the vertices are numbered from zero in the input edge list
+    /// and in the outputs which have no fixed convention (snap, csv, tgf,
+    /// json, nx_adjacency, gml and gexf)
+    #[structopt(name="zero_indexed", long, alias="zero-indexed")]
+    zero_indexed: bool,
+    /// If set, all outputs are gzip compressed (the files written next to
+    /// the subgraph isomorphism instances get a `.gz` suffix)
+    #[structopt(name="gzip", long)]
+    gzip: bool,
+    /// If set, the vertices of the dot output are colored after their degree
+    #[structopt(name="dot_degree", long)]
+    dot_degree: bool,
+    /// If set, the edges of the dot output get thicker as their weight grows
+    #[structopt(name="dot_penwidth", long)]
+    dot_penwidth: bool,
+    /// If set, the dot output does not list the isolated vertices
+    #[structopt(name="dot_compact", long)]
+    dot_compact: bool,
+    /// The header of the dimacs graphs: `bare` (`<n> <m>`) or `edge` (`p edge <n> <m>`)
+    #[structopt(name="dimacs_header", long)]
+    dimacs_header: Option<DimacsHeader>,
+    /// If set, the edge lines of the dimacs graphs carry the weight of the edges
+    #[structopt(name="dimacs_weighted", long)]
+    dimacs_weighted: bool,
+    /// If set, the edge lines of the dimacs graphs do not carry any weight
+    #[structopt(name="dimacs_unweighted", long, conflicts_with="dimacs_weighted")]
+    dimacs_unweighted: bool,
+    /// A custom comment line to add to the dimacs graphs (can be repeated)
+    #[structopt(name="comment", long, number_of_values=1)]
+    comment: Vec<String>,
+    /// The seed of the pseudo-random generation. The same seed (and options)
+    /// always yields the same instance. A random seed is used (and written
+    /// in the output) when none is given
+    #[structopt(name="seed", long)]
+    seed: Option<u64>,
+    /// The seed the weights (of the edges and vertices, and the edge
+    /// attributes) are drawn from. When given, the topology only depends on
+    /// `seed` and the weights only depend on this one
+    #[structopt(name="weight_seed", long, alias="weight-seed")]
+    weight_seed: Option<u64>,
+    /// Gives each vertex a label picked at random among the given ones
+    #[structopt(name="labels", long, use_delimiter=true)]
+    labels: Vec<String>,
+    /// Puts each vertex in a class picked at random in 0..classes
+    #[structopt(name="classes", long)]
+    classes: Option<isize>,
+    /// Gives each edge an additional attribute whose value is picked at random
+    /// among the candidates, e.g. `--attribute capacity=1,2,3` (can be repeated)
+    #[structopt(name="attribute", long, number_of_values=1)]
+    attribute: Vec<Attribute>
 }
+/// An additional edge attribute, along with the candidates for its values
+#[derive(Debug, Clone)]
+struct Attribute {
+    name      : String,
+    candidates: Vec<isize>
+}
+impl FromStr for Attribute {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Attribute, String> {
+        let (name, values) = txt.split_once('=')
+            .ok_or_else(|| format!("{} is not of the form name=v1,v2,..", txt))?;
+        let candidates = values.split(',')
+            .map(|v| v.trim().parse::<isize>().map_err(|e| format!("{}: {}", v, e)))
+            .collect::<Result<Vec<isize>, String>>()?;
+        Ok(Attribute{name: name.trim().to_string(), candidates})
+    }
+}
+/// The (inclusive) bounds `lo:hi` of a range of values
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    lo: isize,
+    hi: isize
+}
+impl FromStr for Bounds {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Bounds, String> {
+        let (lo, hi) = txt.split_once(':')
+            .ok_or_else(|| format!("{} is not of the form lo:hi", txt))?;
+        let lo = lo.trim().parse::<isize>().map_err(|e| format!("{}: {}", lo, e))?;
+        let hi = hi.trim().parse::<isize>().map_err(|e| format!("{}: {}", hi, e))?;
+        Ok(Bounds{lo, hi})
+    }
+}
+/// How the edges of an undirected graph are oriented
+#[derive(Debug, Clone, Copy)]
+enum Orientation {
+    Random, Acyclic
+}
+impl FromStr for Orientation {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Orientation, String> {
+        match txt.to_lowercase().as_str() {
+            "random"  => Ok(Orientation::Random),
+            "acyclic" => Ok(Orientation::Acyclic),
+            _         => Err(txt.to_owned())
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, Default)]
 enum Output {
-    Dimacs, GraphViz
+    #[default]
+    Dimacs, GraphViz, Gexf, MatrixMarket, Json, Csv, Pajek, Metis, Csr, Matrix, Graph6, Sparse6, Gml, Tgf, Snap, AdjacencyJson, Lad
 }
-impl Default for Output {
-    fn default() -> Self {
-        Dimacs
+impl Output {
+    /// Returns the extension of the files written in this language
+    fn extension(self) -> &'static str {
+        match self {
+            Output::Dimacs        => "dimacs",
+            Output::GraphViz      => "dot",
+            Output::Gexf          => "gexf",
+            Output::MatrixMarket  => "mtx",
+            Output::Json          => "json",
+            Output::Csv           => "csv",
+            Output::Pajek         => "net",
+            Output::Metis         => "graph",
+            Output::Csr           => "csr",
+            Output::Matrix        => "matrix",
+            Output::Graph6        => "g6",
+            Output::Sparse6       => "s6",
+            Output::Gml           => "gml",
+            Output::Tgf           => "tgf",
+            Output::Snap          => "snap",
+            Output::AdjacencyJson => "adj.json",
+            Output::Lad           => "lad"
+        }
     }
 }
 impl FromStr for Output {
@@ -71,73 +513,642 @@ impl FromStr for Output {
         if &txt.to_lowercase() == "dot" {
             return Ok(Output::GraphViz);
         }
+        if &txt.to_lowercase() == "gexf" {
+            return Ok(Output::Gexf);
+        }
+        if &txt.to_lowercase() == "mtx" {
+            return Ok(Output::MatrixMarket);
+        }
+        if &txt.to_lowercase() == "json" {
+            return Ok(Output::Json);
+        }
+        if &txt.to_lowercase() == "csv" {
+            return Ok(Output::Csv);
+        }
+        if &txt.to_lowercase() == "pajek" {
+            return Ok(Output::Pajek);
+        }
+        if &txt.to_lowercase() == "metis" {
+            return Ok(Output::Metis);
+        }
+        if &txt.to_lowercase() == "csr" {
+            return Ok(Output::Csr);
+        }
+        if &txt.to_lowercase() == "matrix" {
+            return Ok(Output::Matrix);
+        }
+        if &txt.to_lowercase() == "graph6" {
+            return Ok(Output::Graph6);
+        }
+        if &txt.to_lowercase() == "sparse6" {
+            return Ok(Output::Sparse6);
+        }
+        if &txt.to_lowercase() == "gml" {
+            return Ok(Output::Gml);
+        }
+        if &txt.to_lowercase() == "tgf" {
+            return Ok(Output::Tgf);
+        }
+        if &txt.to_lowercase() == "snap" {
+            return Ok(Output::Snap);
+        }
+        if &txt.to_lowercase() == "nx_adjacency" {
+            return Ok(Output::AdjacencyJson);
+        }
+        if &txt.to_lowercase() == "lad" {
+            return Ok(Output::Lad);
+        }
 
         Err(txt.to_owned())
     }
 }
 
 impl Args {
-    fn graph(&self) -> Graph {
-        let n = if self.max2sat { 2 * self.n } else { self.n };
+    /// Returns the number of vertices of the generated graphs (which must
+    /// be given unless the graph is read from a file)
+    fn n(&self) -> Result<usize, String> {
+        self.n.ok_or_else(|| "the number of vertices (-n) is required to generate this instance".to_string())
+    }
+    /// Returns the likelihood of any edge to be picked (which must be given
+    /// unless the graph is read from a file)
+    fn p(&self) -> Result<f64, String> {
+        self.p.ok_or_else(|| "the probability (-p) is required to generate this instance".to_string())
+    }
+
+    /// Weighs the edges of the graph after the weight options: the weights
+    /// are picked among the candidates (if any) and then post-processed
+    fn weigh(&self, graph: &mut Graph) -> Result<(), String> {
+        if let Some(weights) = self.weights.as_ref() {
+            graph.pluck_random_weights(weights);
+        }
+        if let Some(scale) = self.degree_weights {
+            graph.degree_correlated_weights(scale, self.weight_dist.as_ref())?;
+        } else if let Some(dist) = self.weight_dist.as_ref() {
+            graph.draw_random_weights(dist)?;
+        }
+        if let Some(side) = self.euclidean {
+            graph.euclidean_weights(&Point::random_cloud_with_rng(graph.num_vertices(), side, &mut graph_gen::weight_rng()))?;
+        }
+        if self.shuffle_weights {
+            graph.shuffle_weights(&mut graph_gen::weight_rng());
+        }
+        if let Some(Bounds{lo, hi}) = self.rescale {
+            graph.rescale_weights(lo, hi);
+        }
+        self.negate(graph)?;
+        graph.sign_planted_cut();
+        Ok(())
+    }
+    /// Returns the graph with real weights, weighed after the weight options
+    fn real_graph(&self) -> Result<Graph<f64>, String> {
+        let outputs = if self.formats.is_empty() { vec![self.output.unwrap_or_default()] } else { self.formats.clone() };
+        if let Some(output) = outputs.iter().find(|o| matches!(o, Output::Metis | Output::Csr)) {
+            return Err(format!("the {} output only supports integer weights", output.extension()));
+        }
+
+        let mut graph = self.graph()?.map_weights(|_, w| *w as f64);
+        if let Some(weights) = self.weights.as_ref() {
+            graph.pluck_random_weights(&weights.iter().map(|w| *w as f64).collect::<Vec<f64>>());
+        }
+        if let Some(scale) = self.degree_weights {
+            graph.degree_correlated_weights(scale, self.weight_dist.as_ref())?;
+        } else if let Some(dist) = self.weight_dist.as_ref() {
+            graph.draw_random_weights(dist)?;
+        }
+        if let Some(side) = self.euclidean {
+            graph.euclidean_weights(&Point::random_cloud_with_rng(graph.num_vertices(), side, &mut graph_gen::weight_rng()))?;
+        }
+        if self.shuffle_weights {
+            graph.shuffle_weights(&mut graph_gen::weight_rng());
+        }
+        if let Some(Bounds{lo, hi}) = self.rescale {
+            graph.rescale_weights(lo as f64, hi as f64);
+        }
+        self.negate(&mut graph)?;
+        graph.sign_planted_cut();
+        Ok(graph)
+    }
+    /// Negates a fraction of the edge weights (if asked)
+    fn negate<W: Weight + Neg<Output=W>>(&self, graph: &mut Graph<W>) -> Result<(), String> {
+        match self.negative {
+            None                              => Ok(()),
+            Some(p) if self.no_negative_cycle => graph.negate_weights_without_negative_cycle(p, &mut graph_gen::weight_rng()).map(|_| ()),
+            Some(p)                           => graph.negate_weights(p, &mut graph_gen::weight_rng()).map(|_| ())
+        }
+    }
+    /// Returns true iff the edges get weights (from candidates, from a
+    /// distribution, from an embedding or from the degrees)
+    fn weighted(&self) -> bool {
+        self.weights.is_some() || self.weight_dist.is_some() || self.euclidean.is_some() || self.degree_weights.is_some()
+    }
 
-        let mut model = ErModel::new(n, self.p);
+    fn graph(&self) -> Result<Graph, String> {
+        let mut g = match self.input.as_ref() {
+            None       => self.random_graph()?,
+            Some(path) => self.read(path).map_err(|e| format!("could not read the input graph: {}", e))?
+        };
+        if self.complement {
+            g = g.complement();
+        }
+        if self.reverse {
+            g = g.reverse();
+        }
+        if self.line_graph {
+            g = g.line_graph();
+        }
+        if self.contractions > 0 || self.deletions > 0 {
+            g = g.random_minor(self.contractions, self.deletions);
+        }
+        if let Some(k) = self.core {
+            g = g.k_core(k);
+        }
+        if self.largest_component {
+            g = g.largest_component();
+        }
+        if let Some(policy) = self.simplify {
+            g = g.simplify(policy);
+        }
+        if let Some(policy) = self.undirected {
+            g = g.to_undirected(policy);
+        }
+        match self.orient {
+            None                       => (),
+            Some(Orientation::Random)  => g = g.orient_randomly(&mut graph_gen::rng())?,
+            Some(Orientation::Acyclic) => g = g.orient_acyclically(&mut graph_gen::rng())?
+        }
+        if let Some(fraction) = self.rewire {
+            g = g.rewire(fraction, &mut graph_gen::rng())?;
+        }
+        if self.shuffle {
+            g.shuffle_labels(&mut graph_gen::rng());
+        }
+        if let Some(weights) = self.vertex_weights.as_ref() {
+            g.pluck_random_vertex_weights(weights);
+        }
+        if let Some(dist) = self.vertex_weight_dist.as_ref() {
+            g.draw_random_vertex_weights(dist)?;
+        }
+        if !self.labels.is_empty() {
+            g.pluck_random_labels(&self.labels);
+        }
+        if let Some(classes) = self.classes {
+            g.pluck_random_classes(&(0..classes).collect::<Vec<isize>>());
+        }
+        for attribute in self.attribute.iter() {
+            g.pluck_random_attribute(&attribute.name, &attribute.candidates);
+        }
+        Ok(g)
+    }
+
+    /// Reads the graph from the file at the given path
+    fn read(&self, path: &str) -> Result<Graph, String> {
+        let input = BufReader::new(File::open(path).map_err(|e| e.to_string())?);
+        if self.edge_list {
+            Graph::read_edge_list(input, self.digraph, self.zero_indexed)
+        } else {
+            let mut g = Graph::read_dimacs(input, self.digraph)?;
+            g.set_zero_indexed(self.zero_indexed);
+            Ok(g)
+        }
+    }
+
+    /// Returns a new random graph (sampled on all cores if asked)
+    fn random_graph(&self) -> Result<Graph, String> {
+        #[cfg(feature = "rayon")]
+        if self.parallel {
+            return Ok(self.er_generator()?.gen_parallel());
+        }
+        Ok(self.generator()?.gen())
+    }
+
+    /// Returns the generator of the random graphs (or an error message when
+    /// the parameters of the model are invalid)
+    fn generator(&self) -> Result<Box<dyn GraphGenerator>, String> {
+        Ok(Box::new(self.er_generator()?))
+    }
+
+    /// Returns the Erdos-Renyi generator of the random graphs (or an error
+    /// message when the parameters of the model are invalid)
+    fn er_generator(&self) -> Result<ErGenerator, String> {
+        let model = self.model()?;
+        let generator = match self.seed {
+            None       => model.try_generator(),
+            Some(seed) => model.try_generator_with_seed(seed)
+        };
+        generator.map_err(|e| e.to_string())
+    }
+
+    /// Returns the Erdos-Renyi model of the random graphs
+    fn model(&self) -> Result<ErModel, String> {
+        let n = if self.max2sat { 2 * self.n()? } else { self.n()? };
+
+        let mut model = ErModel::new(n, self.p()?);
 
         if self.digraph {
             model = model.digraph();
         }
 
+        if self.oriented {
+            model = model.oriented();
+        }
+
+        if self.bernoulli {
+            model = model.bernoulli();
+        }
+
         if self.loops {
             model = model.with_self_loops();
         }
 
-        model.generator().gen()
+        if let Some(q) = self.loop_probability {
+            model = model.with_loop_probability(q);
+        }
+
+        if self.zero_indexed {
+            model = model.zero_indexed();
+        }
+
+        if let Some(connectivity) = self.connected {
+            model = model.connected(connectivity);
+        }
+
+        if self.no_isolated {
+            model = model.without_isolated_vertices();
+        }
+
+        if let Some(d) = self.max_degree {
+            model = model.max_degree(d);
+        }
+
+        if let Some(d) = self.min_degree {
+            model = model.min_degree(d);
+        }
+
+        if self.triangle_free {
+            model = model.triangle_free();
+        }
+
+        if let Some(g) = self.girth {
+            model = model.girth(g);
+        }
+
+        if let Some(k) = self.independent_set {
+            model = model.planted_independent_set(k);
+        }
+
+        if let Some(k) = self.vertex_cover {
+            model = model.planted_vertex_cover(k);
+        }
+
+        if let Some(bias) = self.planted_cut {
+            model = model.planted_cut(bias);
+        }
+
+        Ok(model)
     }
 
     fn wcnf(&self, g: Graph) -> Max2SatGraph {
         Max2SatGraph::new(g)
     }
 
-    fn generatable(&self) -> Generatable {
-        let mut graph = self.graph();
+    fn ddo_preset(&self) -> Option<DdoPreset> {
+        if self.ddo_misp {
+            Some(DdoPreset::Misp)
+        } else if self.ddo_mcp {
+            Some(DdoPreset::Mcp)
+        } else if self.ddo_max2sat {
+            Some(DdoPreset::Max2Sat)
+        } else {
+            None
+        }
+    }
 
-        if self.max2sat {
+    fn generatable(&self) -> Result<Generatable, String> {
+        if let Some(preset) = self.ddo_preset() {
+            return preset.try_generate(self.n()?, self.p()?, self.weights.as_deref()).map_err(|e| e.to_string());
+        }
+        if self.atsp {
+            let mut t = AtspInstance::new(self.n()?, self.max_cost);
             if let Some(weights) = self.weights.as_ref() {
-                graph.pluck_random_weights(weights);
+                t.pluck_random_weights(weights);
             }
 
-            Generatable::GenSat   {s : self.wcnf(graph)}
+            return Ok(Generatable::Atsp {t});
+        }
+        if self.cvrp {
+            let c = CvrpInstance::new(self.n()?, self.capacity, self.max_demand, self.side);
+            return Ok(Generatable::Cvrp {c});
+        }
+        if let Some(k) = self.chromatic {
+            let c = PlantedColoring::try_new(self.n()?, k, self.p()?).map_err(|e| e.to_string())?;
+            return Ok(Generatable::Chromatic {c});
+        }
+        if let Some(c) = self.clique_cover {
+            let c = CliqueCoverInstance::try_new(self.n()?, c, self.p()?).map_err(|e| e.to_string())?;
+            return Ok(Generatable::CliqueCover {c});
+        }
+        if let Some(k) = self.feedback {
+            let f = PlantedFeedbackSet::try_new(self.n()?, k, self.p()?).map_err(|e| e.to_string())?;
+            return Ok(Generatable::Feedback {f});
+        }
+        if let Some(k) = self.treewidth {
+            let t = PartialKTree::try_new(self.n()?, k, self.p()?).map_err(|e| e.to_string())?;
+            return Ok(Generatable::KTree {t});
+        }
+        if self.matching {
+            let mut m = if self.bipartite {
+                MatchingInstance::bipartite(self.n()?, self.p()?)
+            } else {
+                MatchingInstance::new(self.graph()?)
+            };
+            self.weigh(m.graph_mut())?;
+            if self.plant {
+                m.plant_perfect_matching();
+            }
+            if self.blossom {
+                m = m.blossom();
+            }
+
+            return Ok(Generatable::Matching {m});
+        }
+
+        let mut graph = self.graph()?;
+
+        if self.max2sat {
+            self.weigh(&mut graph)?;
+
+            Ok(Generatable::GenSat   {s : self.wcnf(graph)})
+        } else if let Some(k) = self.coloring {
+            Ok(Generatable::ColSat   {s : ColoringSatGraph::new(graph, k)})
+        } else if self.qubo || self.ising {
+            self.weigh(&mut graph)?;
+
+            let mut q = MaxCutQubo::new(graph);
+            if self.ising {
+                q = q.ising();
+            }
+
+            Ok(Generatable::CutQubo  {q})
+        } else if let Some(problem) = self.lp {
+            self.weigh(&mut graph)?;
+
+            Ok(Generatable::IlpGraph {g: IlpGraph::new(graph, problem)})
+        } else if self.iso {
+            self.weigh(&mut graph)?;
+
+            Ok(Generatable::IsoPair  {p: IsoPair::new(graph, self.perturbations)})
+        } else if let Some(k) = self.subiso {
+            self.weigh(&mut graph)?;
+
+            Ok(Generatable::SubIso   {p: SubIsoPair::try_new(graph, k).map_err(|e| e.to_string())?})
+        } else if self.mcf {
+            self.weigh(&mut graph)?;
+
+            Ok(Generatable::MinCostFlow {f: MinCostFlowInstance::new(graph, &self.capacities, &self.costs)?})
+        } else if self.metis {
+            self.weigh(&mut graph)?;
+            let g = if self.weighted() {
+                MetisGraph::new(graph).with_edge_weights()
+            } else {
+                MetisGraph::new(graph)
+            };
+
+            Ok(Generatable::Metis    {g})
         } else if self.misp {
             let mut g = WeightedMaxCliqueGraph::new(graph);
+            let style = self.dimacs_style(g.style().clone());
+            g = g.with_style(style);
 
             if let Some(weights) = self.weights.as_ref() {
                 g.pluck_random_weights(weights);
             }
 
-            Generatable::ClqGraph {g}
+            Ok(Generatable::ClqGraph {g})
         } else {
-            if let Some(weights) = self.weights.as_ref() {
-                graph.pluck_random_weights(weights);
-            }
+            self.weigh(&mut graph)?;
 
-            Generatable::GenGraph {g : graph}
+            Ok(Generatable::GenGraph {g : graph})
         }
     }
 
-    fn output(&self, g: &Generatable) -> String {
-        match &self.output {
-            None => g.to_dimacs(),
-            Some(o) => match o {
-                Output::Dimacs   => g.to_dimacs(),
-                Output::GraphViz => g.to_dot()
-            }
+    fn write<W: Write>(&self, g: &Generatable, output: Output, out: W) -> io::Result<()> {
+        match output {
+            Output::Dimacs        => self.write_dimacs(g, out),
+            Output::GraphViz      => match self.dot_style() {
+                None        => g.write_dot(out),
+                Some(style) => Self::underlying(g)?.write_dot_styled(out, &style)
+            },
+            Output::Gexf          => Self::underlying(g)?.write_gexf(out),
+            Output::MatrixMarket  => Self::underlying(g)?.write_mtx(out),
+            Output::Json          => Self::underlying(g)?.write_json(out),
+            Output::Csv           => Self::underlying(g)?.write_csv(out, !self.no_header),
+            Output::Pajek         => Self::underlying(g)?.write_pajek(out),
+            Output::Metis         => Self::underlying(g)?.write_metis(out),
+            Output::Csr           => Self::underlying(g)?.write_csr(out),
+            Output::Matrix        => Self::underlying(g)?.write_adjacency_matrix(out, self.weighted()),
+            Output::Graph6        => Self::underlying(g)?.write_graph6(out),
+            Output::Sparse6       => Self::underlying(g)?.write_sparse6(out),
+            Output::Gml           => Self::underlying(g)?.write_gml(out),
+            Output::Tgf           => Self::underlying(g)?.write_tgf(out),
+            Output::Snap          => Self::underlying(g)?.write_snap(out),
+            Output::AdjacencyJson => Self::underlying(g)?.write_adjacency_json(out),
+            Output::Lad           => Self::underlying(g)?.write_lad(out)
+        }
+    }
+
+    /// Writes the graph with real weights in the given output language
+    fn write_real<W: Write>(&self, g: &Graph<f64>, output: Output, out: W) -> io::Result<()> {
+        match output {
+            Output::Dimacs        => g.write_dimacs_styled(out, &self.dimacs_style(DimacsStyle::new())),
+            Output::GraphViz      => g.write_dot_styled(out, &self.dot_style().unwrap_or_default()),
+            Output::Gexf          => g.write_gexf(out),
+            Output::MatrixMarket  => g.write_mtx(out),
+            Output::Json          => g.write_json(out),
+            Output::Csv           => g.write_csv(out, !self.no_header),
+            Output::Pajek         => g.write_pajek(out),
+            Output::Matrix        => g.write_adjacency_matrix(out, self.weighted()),
+            Output::Graph6        => g.write_graph6(out),
+            Output::Sparse6       => g.write_sparse6(out),
+            Output::Gml           => g.write_gml(out),
+            Output::Tgf           => g.write_tgf(out),
+            Output::Snap          => g.write_snap(out),
+            Output::AdjacencyJson => g.write_adjacency_json(out),
+            Output::Lad           => g.write_lad(out),
+            Output::Metis | Output::Csr =>
+                Err(io::Error::new(io::ErrorKind::InvalidInput, "this output only supports integer weights"))
+        }
+    }
+
+    fn write_dimacs<W: Write>(&self, g: &Generatable, out: W) -> io::Result<()> {
+        match g {
+            Generatable::GenGraph{g} => g.write_dimacs_styled(out, &self.dimacs_style(DimacsStyle::new())),
+            _                        => g.write_dimacs(out)
+        }
+    }
+
+    /// Returns the given dimacs style amended with the command line options
+    fn dimacs_style(&self, mut style: DimacsStyle) -> DimacsStyle {
+        if let Some(header) = self.dimacs_header {
+            style = style.header(header);
+        }
+        if self.dimacs_weighted {
+            style = style.weighted(true);
+        }
+        if self.dimacs_unweighted {
+            style = style.weighted(false);
+        }
+        for comment in self.comment.iter() {
+            style = style.comment(comment.clone());
+        }
+        style
+    }
+
+    /// Returns the styling of the dot output (if any was asked)
+    fn dot_style(&self) -> Option<DotStyle> {
+        if !(self.dot_degree || self.dot_penwidth || self.dot_compact) {
+            return None;
+        }
+        let mut style = DotStyle::new();
+        if self.dot_degree {
+            style = style.color_by_degree();
+        }
+        if self.dot_penwidth {
+            style = style.weighted_edges();
+        }
+        if self.dot_compact {
+            style = style.without_isolated();
+        }
+        Some(style)
+    }
+
+    /// Writes the statistics of the graph to <prefix>.stats.json
+    fn write_stats_json<W>(&self, g: &Graph<W>) -> Result<(), String> {
+        let prefix = self.prefix.as_ref().expect("the stats sidecar requires a prefix");
+        to_file(format!("{}.stats.json", prefix), false, |out| g.write_stats_json(out, self.seed))
+            .map_err(|e| format!("could not write the stats: {}", e))
+    }
+
+    /// Reports how the graph deviates from the expectations of its model
+    fn check_model<W>(&self, g: &Graph<W>) -> Result<(), String> {
+        let model = self.model()?;
+        if model.has_constraints() {
+            eprintln!("warning: the expectations are those of the unconstrained model");
+        }
+        for comparison in model.compare(g) {
+            eprintln!("{}", comparison);
+        }
+        Ok(())
+    }
+
+    /// Returns the graph to write in a general purpose graph format (or an
+    /// error when the instance has none)
+    fn underlying(g: &Generatable) -> io::Result<&Graph> {
+        g.graph().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "this instance cannot be written in a graph format"))
+    }
+}
+
+/// Streams the output of `write` to the given writer (through a gzip
+/// encoder when `gzip` is set)
+fn stream<W, F>(out: W, gzip: bool, write: F) -> io::Result<()>
+    where W: Write,
+          F: FnOnce(&mut dyn Write) -> io::Result<()>
+{
+    let mut out = if gzip {
+        gzipped(BufWriter::new(out), |gz| write(gz))?
+    } else {
+        let mut out = BufWriter::new(out);
+        write(&mut out)?;
+        out
+    };
+    out.flush()
+}
+
+/// Streams the output of `write` to the file at the given path
+fn to_file<F>(path: String, gzip: bool, write: F) -> io::Result<()>
+    where F: FnOnce(&mut dyn Write) -> io::Result<()>
+{
+    let path = if gzip { format!("{}.gz", path) } else { path };
+    stream(File::create(path)?, gzip, write)
+}
+
+/// Returns the value of the result, or reports its error and exits
+fn or_exit<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    })
+}
+
+/// Writes the instance in the output language(s) asked on the command line,
+/// either on the standard output or in the files starting with the prefix
+fn emit<F>(args: &Args, write: F)
+    where F: Fn(Output, &mut dyn Write) -> io::Result<()>
+{
+    if args.formats.is_empty() {
+        let stdout = io::stdout();
+        let output = args.output.unwrap_or_default();
+        or_exit(stream(stdout.lock(), args.gzip, |out| write(output, out))
+            .map_err(|e| format!("could not write the output: {}", e)));
+    } else {
+        let prefix = args.prefix.as_ref().expect("formats require a prefix");
+        for output in args.formats.iter().copied() {
+            or_exit(to_file(format!("{}.{}", prefix, output.extension()), args.gzip, |out| write(output, out))
+                .map_err(|e| format!("could not write the {} output: {}", output.extension(), e)));
         }
     }
 }
 
 fn main() {
-    let args = Args::from_args();
-    let graph= args.generatable();
-    let out  = args.output(&graph);
+    let mut args = Args::from_args();
+    let seed = *args.seed.get_or_insert_with(|| thread_rng().gen());
+    graph_gen::seed(seed);
+    if let Some(seed) = args.weight_seed {
+        graph_gen::seed_weights(seed);
+    }
 
-    println!("{}", out);
+    if args.real_weights {
+        let graph = or_exit(args.real_graph());
+        emit(&args, |output, out| args.write_real(&graph, output, out));
+        if args.stats {
+            or_exit(graph.write_stats(io::stderr().lock()).map_err(|e| format!("could not write the stats: {}", e)));
+        }
+        if args.summary {
+            eprintln!("{}", graph.stats());
+        }
+        if args.stats_json {
+            or_exit(args.write_stats_json(&graph));
+        }
+        if args.check_model {
+            or_exit(args.check_model(&graph));
+        }
+        return;
+    }
+
+    let graph = or_exit(args.generatable());
+    emit(&args, |output, out| args.write(&graph, output, out));
+    if args.stats || args.summary || args.stats_json || args.check_model {
+        let g = or_exit(graph.graph().ok_or_else(|| "this instance has no graph to compute stats on".to_string()));
+        if args.stats {
+            or_exit(g.write_stats(io::stderr().lock()).map_err(|e| format!("could not write the stats: {}", e)));
+        }
+        if args.summary {
+            eprintln!("{}", g.stats());
+        }
+        if args.stats_json {
+            or_exit(args.write_stats_json(g));
+        }
+        if args.check_model {
+            or_exit(args.check_model(g));
+        }
+    }
+
+    let gzip = args.gzip;
+    if let (Some(prefix), Generatable::SubIso{p}) = (args.prefix.as_ref(), &graph) {
+        or_exit(to_file(format!("{}.pattern.lad", prefix), gzip, |out| p.pattern().write_lad(out))
+            .map_err(|e| format!("could not write the pattern output: {}", e)));
+        or_exit(to_file(format!("{}.target.lad",  prefix), gzip, |out| p.target().write_lad(out))
+            .map_err(|e| format!("could not write the target output: {}", e)));
+        or_exit(to_file(format!("{}.embedding",   prefix), gzip, |out| p.write_certificate(out))
+            .map_err(|e| format!("could not write the embedding output: {}", e)));
+    }
 }
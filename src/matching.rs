@@ -0,0 +1,228 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module produces maximum weighted matching benchmarks, either on
+//! general graphs or on bipartite ones. Optionally, a perfect matching of
+//! known optimal weight can be planted in the instance.
+
+use crate::{Edge, Graph, GraphMeta, Vertex};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::seq::SliceRandom;
+use std::io::{self, Write};
+
+/// A maximum weighted matching instance
+#[derive(Debug, Clone)]
+pub struct MatchingInstance {
+    g      : Graph,
+    /// The number of vertices on the left side (when the graph is bipartite).
+    /// The left vertices are numbered 1..=left and the other ones on the
+    /// right.
+    left   : Option<usize>,
+    /// The planted matching (if any)
+    planted: Vec<Edge>,
+    /// True iff the output is meant for Blossom V rather than DIMACS
+    blossom: bool
+}
+impl MatchingInstance {
+    /// Creates a matching instance on a general graph
+    pub fn new(g: Graph) -> Self {
+        MatchingInstance{g, left: None, planted: vec![], blossom: false}
+    }
+    /// Creates a matching instance on a random bipartite graph having `n`
+    /// vertices on each side where each of the n² candidate edges appears
+    /// with probability p.
+    pub fn bipartite(n: usize, p: f64) -> Self {
+        let mut rng = crate::random::rng();
+        let coin    = Bernoulli::new(p.clamp(0.0, 1.0)).unwrap();
+
+        let mut g = Graph::empty(GraphMeta::new(format!("bipartite graph with {} vertices on each side (p = {}){}", n, p, crate::random::seed_suffix(crate::random::thread_seed()))), 2 * n);
+        for u in 1..=n as isize {
+            for v in 1..=n as isize {
+                if coin.sample(&mut rng) {
+                    g.list.insert(Edge{src: Vertex{id: u}, dst: Vertex{id: n as isize + v}}, 1);
+                }
+            }
+        }
+
+        MatchingInstance{g, left: Some(n), planted: vec![], blossom: false}
+    }
+    /// Write the instance in the format of Blossom V rather than DIMACS
+    pub fn blossom(self) -> Self {
+        MatchingInstance{blossom: true, ..self}
+    }
+
+    /// Returns the graph underlying this instance
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+    /// Returns the graph underlying this instance so that its edges can be
+    /// weighed (before a perfect matching is planted)
+    pub fn graph_mut(&mut self) -> &mut Graph {
+        &mut self.g
+    }
+
+    pub fn pluck_random_weights(&mut self, from: &[isize]) {
+        self.g.pluck_random_weights(from)
+    }
+
+    /// Plants a random perfect matching (or a matching leaving one vertex
+    /// unmatched when the number of vertices is odd) whose edges all weigh
+    /// one more than the heaviest other edge. This matching is then the
+    /// unique maximum weighted matching: with `H` the weight of the planted
+    /// edges, setting the dual value `H/2` on each vertex certifies that no
+    /// matching can weigh more.
+    pub fn plant_perfect_matching(&mut self) {
+        let mut rng = crate::random::rng();
+
+        let heavy = self.g.list.values().copied().max().unwrap_or(0).max(0) + 1;
+        let mut planted = vec![];
+        match self.left {
+            Some(n) => {
+                let mut right = (n as isize + 1..=2 * n as isize).collect::<Vec<isize>>();
+                right.shuffle(&mut rng);
+                for (u, v) in (1..=n as isize).zip(right) {
+                    planted.push(Edge{src: Vertex{id: u}, dst: Vertex{id: v}});
+                }
+            },
+            None => {
+                let mut vertices = (1..=self.g.n as isize).collect::<Vec<isize>>();
+                vertices.shuffle(&mut rng);
+                for pair in vertices.chunks_exact(2) {
+                    planted.push(Edge{src: Vertex{id: pair[0]}, dst: Vertex{id: pair[1]}});
+                }
+            }
+        }
+
+        for edge in planted.iter() {
+            self.g.list.remove(&edge.rev());
+            self.g.list.insert(*edge, heavy);
+        }
+        self.planted = planted;
+    }
+
+    /// Returns the edges of the instance (self loops are irrelevant)
+    fn edges(&self) -> Vec<(Edge, isize)> {
+        let mut edges = self.g.list.iter()
+            .filter(|(e, _)| !e.is_self_loop())
+            .map(|(e, w)| (*e, *w))
+            .collect::<Vec<(Edge, isize)>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Returns the weight of the planted matching (if any)
+    fn planted_weight(&self) -> Option<isize> {
+        if self.planted.is_empty() {
+            None
+        } else {
+            Some(self.planted.iter().map(|e| self.g.list[e]).sum())
+        }
+    }
+
+    /// Writes the instance in the DIMACS format. Bipartite instances use the
+    /// assignment (`p asn`) flavor of the format where the left vertices are
+    /// listed as `n` lines and the edges as `a` lines. General instances use
+    /// the `p edge` flavor with `e <src> <dst> <weight>` lines.
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let edges = self.edges();
+        let graph = if self.left.is_some() { self.g.meta.provenance.clone() } else { format!("graph from {}", self.g.meta.provenance) };
+        writeln!(out, "c Pseudo-random maximum weighted matching instance on a {}", graph)?;
+        writeln!(out, "c This graph has {} vertices and {} edges", self.g.n, edges.len())?;
+        if let Some(w) = self.planted_weight() {
+            writeln!(out, "c A matching of {} edges and weight {} was planted: it is optimal", self.planted.len(), w)?;
+        }
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+
+        match self.left {
+            Some(n) => {
+                writeln!(out, "p asn {} {}", self.g.n, edges.len())?;
+                for v in 1..=n {
+                    writeln!(out, "n {}", v)?;
+                }
+                for (e, w) in edges.iter() {
+                    writeln!(out, "a {} {} {}", e.src.id, e.dst.id, w)?;
+                }
+            },
+            None => {
+                writeln!(out, "p edge {} {}", self.g.n, edges.len())?;
+                for (e, w) in edges.iter() {
+                    writeln!(out, "e {} {} {}", e.src.id, e.dst.id, w)?;
+                }
+            }
+        }
+
+        if !self.planted.is_empty() {
+            writeln!(out, "c Planted matching")?;
+            for e in self.planted.iter() {
+                writeln!(out, "c m {} {}", e.src.id, e.dst.id)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the instance in the format of Blossom V: a `<n> <m>` header
+    /// followed by one `<src> <dst> <weight>` line per edge. The vertices are
+    /// numbered from zero, and no comment is allowed.
+    pub fn to_blossom(&self) -> String {
+        crate::written(|out| self.write_blossom(out))
+    }
+
+    /// Same as `to_blossom` but streams the output to the given writer
+    pub fn write_blossom<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let edges = self.edges();
+        writeln!(out, "{} {}", self.g.n, edges.len())?;
+        for (e, w) in edges.iter() {
+            writeln!(out, "{} {} {}", e.src.id - 1, e.dst.id - 1, w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the instance either in DIMACS or Blossom V format depending on
+    /// the configuration
+    pub fn to_text(&self) -> String {
+        if self.blossom {
+            self.to_blossom()
+        } else {
+            self.to_dimacs()
+        }
+    }
+    pub fn write_text<W: Write>(&self, out: W) -> io::Result<()> {
+        if self.blossom {
+            self.write_blossom(out)
+        } else {
+            self.write_dimacs(out)
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
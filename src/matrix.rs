@@ -0,0 +1,57 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes the dense adjacency matrix of (small) graphs, as is
+//! expected by MATLAB or NumPy scripts.
+
+use crate::Graph;
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Writes the adjacency matrix of the graph: one line per row, the
+    /// entries being separated by a space. The entry on row `i` and column
+    /// `j` is the weight of the edge `i -> j` when `weighted` is true, and 1
+    /// otherwise. It is 0 when there is no such edge. The matrix of an
+    /// undirected graph is symmetric.
+    pub fn to_adjacency_matrix(&self, weighted: bool) -> String {
+        crate::written(|out| self.write_adjacency_matrix(out, weighted))
+    }
+
+    /// Same as `to_adjacency_matrix` but streams the output to the given
+    /// writer
+    pub fn write_adjacency_matrix<W: Write>(&self, mut out: W, weighted: bool) -> io::Result<()> {
+        let mut matrix = vec![vec!["0".to_string(); self.n]; self.n];
+        for (e, w) in self.list.iter() {
+            let value = if weighted { w.to_string() } else { "1".to_string() };
+            let (i, j) = (e.src.id as usize - 1, e.dst.id as usize - 1);
+            if !self.meta.digraph {
+                matrix[j][i] = value.clone();
+            }
+            matrix[i][j] = value;
+        }
+
+        for row in matrix.iter() {
+            writeln!(out, "{}", row.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
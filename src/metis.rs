@@ -0,0 +1,142 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the METIS `.graph` format which is consumed
+//! by the METIS and KaHIP graph partitioners. Both the vertices and the
+//! edges can optionally be weighted (as told by the `fmt` flags of the
+//! header).
+
+use crate::Graph;
+use rand::distributions::{Distribution, Uniform};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+impl Graph {
+    /// Writes the graph in the plain METIS format: a `<n> <m>` header and
+    /// then one line per vertex listing its neighbours. METIS only deals with
+    /// undirected graphs without self loops; so the loops are dropped and
+    /// the arcs of a digraph are written as undirected edges.
+    pub fn to_metis(&self) -> String {
+        crate::written(|out| self.write_metis(out))
+    }
+
+    /// Same as `to_metis` but streams the output to the given writer
+    pub fn write_metis<W: Write>(&self, out: W) -> io::Result<()> {
+        self.metis(out, None, false)
+    }
+
+    /// Writes the graph in the METIS format: a `<n> <m> [fmt]` header and
+    /// then one line per vertex listing its (optional) weight followed by its
+    /// neighbours (each of which is followed by the weight of the edge when
+    /// edges are weighted). The weights of anti-parallel arcs are summed.
+    fn metis<W: Write>(&self, mut out: W, vertex_weights: Option<&[isize]>, edge_weights: bool) -> io::Result<()> {
+        let mut adj = vec![BTreeMap::new(); self.n];
+        for (e, w) in self.list.iter().filter(|(e, _)| !e.is_self_loop()) {
+            *adj[e.src.id as usize - 1].entry(e.dst.id).or_insert(0) += *w;
+            *adj[e.dst.id as usize - 1].entry(e.src.id).or_insert(0) += *w;
+        }
+        let m = adj.iter().map(|a| a.len()).sum::<usize>() / 2;
+
+        writeln!(out, "% Graph from {}", self.meta.provenance)?;
+        writeln!(out, "% Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        match fmt(vertex_weights.is_some(), edge_weights) {
+            None      => writeln!(out, "{} {}", self.n, m)?,
+            Some(fmt) => writeln!(out, "{} {} {}", self.n, m, fmt)?
+        }
+
+        for (i, neighbours) in adj.iter().enumerate() {
+            let mut line = vec![];
+            if let Some(weights) = vertex_weights {
+                line.push(weights[i].to_string());
+            }
+            for (v, w) in neighbours.iter() {
+                line.push(v.to_string());
+                if edge_weights {
+                    line.push(w.to_string());
+                }
+            }
+            writeln!(out, "{}", line.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the `fmt` flags of the header (if any is needed)
+fn fmt(vertex_weights: bool, edge_weights: bool) -> Option<&'static str> {
+    match (vertex_weights, edge_weights) {
+        (false, false) => None,
+        (false, true ) => Some("001"),
+        (true , false) => Some("010"),
+        (true , true ) => Some("011")
+    }
+}
+
+/// A graph partitioning instance in the METIS format
+#[derive(Debug, Clone)]
+pub struct MetisGraph {
+    g: Graph,
+    /// The weight of each vertex (if they are weighted)
+    vertex_weights: Option<Vec<isize>>,
+    /// True iff the edges weights are to be written
+    edge_weights  : bool
+}
+impl MetisGraph {
+    /// The vertices of the instance weigh what they weigh in the graph (if
+    /// they have weights)
+    pub fn new(g: Graph) -> Self {
+        let vertex_weights = g.vertex_weights();
+        MetisGraph{g, vertex_weights, edge_weights: false}
+    }
+    /// Write the edges weights along with the adjacency lists
+    pub fn with_edge_weights(self) -> Self {
+        MetisGraph{edge_weights: true, ..self}
+    }
+
+    /// Returns the graph underlying this instance
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+    /// Weights the vertices with random weights picked from the candidates
+    pub fn pluck_random_vertex_weights(&mut self, from: &[isize]) {
+        let mut rng = crate::random::weight_rng();
+        let dist= Uniform::new(0, from.len());
+
+        self.vertex_weights = Some((0..self.g.n).map(|_| from[dist.sample(&mut rng)]).collect());
+    }
+
+    /// Writes the graph in the METIS format (see `Graph::to_metis`) along
+    /// with the configured weights.
+    pub fn to_metis(&self) -> String {
+        crate::written(|out| self.write_metis(out))
+    }
+
+    /// Same as `to_metis` but streams the output to the given writer
+    pub fn write_metis<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.metis(out, self.vertex_weights.as_deref(), self.edge_weights)
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
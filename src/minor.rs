@@ -0,0 +1,108 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module produces minors of the graphs: graphs obtained by contracting
+//! and deleting edges. Random minors of a base graph are a simple way to get
+//! families of correlated instances.
+
+use crate::{Edge, Graph, Vertex};
+use rand::seq::SliceRandom;
+
+impl<W: Clone> Graph<W> {
+    /// Returns the graph where the edge `src -> dst` is contracted: `dst` is
+    /// merged into `src` (keeping the label, class and weight of `src`) and
+    /// the vertices after `dst` are renumbered so that the ids remain
+    /// contiguous. The edges of `dst` are moved to `src` unless `src` already
+    /// has the same edge, and the edges which would become self loops are
+    /// dropped (the existing self loops are kept). An error is returned when
+    /// the edge does not belong to the graph or is a self loop.
+    pub fn contract_edge(&self, edge: Edge) -> Result<Graph<W>, String> {
+        let edge = self.stored(edge)
+            .ok_or_else(|| format!("({}, {}) is not an edge of the graph", edge.src.id, edge.dst.id))?;
+        if edge.is_self_loop() {
+            return Err(format!("the self loop on {} cannot be contracted", edge.src.id));
+        }
+
+        let merged  = edge.dst.id;
+        let shifted = |id: isize| if id > merged { id - 1 } else { id };
+        let moved   = |v: Vertex| match v.id {
+            id if id == merged => Vertex{id: shifted(edge.src.id)},
+            id                 => Vertex{id: shifted(id)}
+        };
+
+        // the arcs u -> v and v -> w become anti-parallel once v and w merge
+        let mut meta  = self.derived_meta("contraction");
+        meta.oriented = false;
+        let mut g     = Graph::empty(meta, self.n - 1);
+        for v in self.vertices().filter(|v| v.id != merged) {
+            self.labels.copy(v.id, &mut g.labels, moved(v).id);
+        }
+
+        let mut edges = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        for e in edges {
+            let contracted = Edge{src: moved(e.src), dst: moved(e.dst)};
+            if contracted.is_self_loop() && !e.is_self_loop() {
+                continue;
+            }
+            if g.stored(contracted).is_some() {
+                continue;
+            }
+            g.list.insert(contracted, self.list[&e].clone());
+            for (name, values) in self.attrs.iter() {
+                if let Some(value) = values.get(&e) {
+                    g.attrs.entry(name.clone()).or_default().insert(contracted, *value);
+                }
+            }
+        }
+        Ok(g)
+    }
+
+    /// Returns a random minor of the graph: `contractions` random edges
+    /// (which are not self loops) are contracted first, and then
+    /// `deletions` random edges are deleted. Fewer edges are contracted
+    /// (resp. deleted) when the graph runs out of them.
+    pub fn random_minor(&self, contractions: usize, deletions: usize) -> Graph<W> {
+        let mut rng = crate::random::rng();
+        let mut g   = self.clone();
+
+        for _ in 0..contractions {
+            let mut candidates = g.list.keys().filter(|e| !e.is_self_loop()).copied().collect::<Vec<Edge>>();
+            candidates.sort_unstable();
+            match candidates.choose(&mut rng) {
+                None       => break,
+                Some(edge) => g = g.contract_edge(*edge).expect("the edge belongs to the graph")
+            }
+        }
+
+        // the contractions have forgotten the planted certificate (if any),
+        // but the deletions keep a planted independent set, vertex cover or
+        // maximum cut valid
+        let mut edges = g.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        for edge in edges.choose_multiple(&mut rng, deletions) {
+            g.remove_edge(edge.src, edge.dst);
+        }
+
+        g.meta = self.derived_meta(&format!("random minor ({} contractions, {} deletions)", contractions, deletions));
+        g.meta.oriented = self.meta.oriented && contractions == 0;
+        g
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the MatrixMarket coordinate format which is
+//! what most sparse linear algebra tools expect.
+
+use crate::Graph;
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Writes the (weighted) adjacency matrix of the graph in the
+    /// MatrixMarket coordinate format. The matrix of an undirected graph is
+    /// `symmetric` (only its lower triangle is written) whereas that of a
+    /// digraph is `general`. The entries are the real weights of the edges.
+    pub fn to_mtx(&self) -> String {
+        crate::written(|out| self.write_mtx(out))
+    }
+
+    /// Same as `to_mtx` but streams the output to the given writer
+    pub fn write_mtx<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let symmetry = if self.meta.digraph { "general" } else { "symmetric" };
+        writeln!(out, "%%MatrixMarket matrix coordinate real {}", symmetry)?;
+        writeln!(out, "% Graph from {}", self.meta.provenance)?;
+        writeln!(out, "% Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "{} {} {}", self.n, self.n, self.list.len())?;
+
+        for (edge, w) in self.list.iter() {
+            let (row, col) = if self.meta.digraph {
+                (edge.src.id, edge.dst.id)
+            } else {
+                (edge.src.id.max(edge.dst.id), edge.src.id.min(edge.dst.id))
+            };
+            writeln!(out, "{} {} {}", row, col, w)?;
+        }
+
+        Ok(())
+    }
+}
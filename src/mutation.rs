@@ -0,0 +1,89 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module lets the graphs be modified after they were generated (e.g.
+//! to add a few handcrafted edges before exporting them). The edges are
+//! never duplicated, and the digraph/self loops flags of the model are
+//! respected.
+
+use crate::{Edge, Graph, Vertex};
+use std::sync::OnceLock;
+
+impl<W> Graph<W> {
+    /// Returns the edge as it is stored in the graph (in an undirected graph,
+    /// it can be stored in either orientation) if it belongs to the graph
+    pub(crate) fn stored(&self, edge: Edge) -> Option<Edge> {
+        if self.list.contains_key(&edge) {
+            Some(edge)
+        } else if !self.meta.digraph && self.list.contains_key(&edge.rev()) {
+            Some(edge.rev())
+        } else {
+            None
+        }
+    }
+
+    /// Adds a new (isolated) vertex to the graph and returns it
+    pub fn add_vertex(&mut self) -> Vertex {
+        self.n  += 1;
+        self.adj = OnceLock::new();
+        Vertex{id: self.n as isize}
+    }
+    /// Adds the edge `src -> dst` with the given weight. It returns false
+    /// (and leaves the graph untouched) when the edge already belongs to the
+    /// graph, and an error when the edge is a self loop but the model does
+    /// not allow them or when an end is not a vertex of the graph. The
+    /// certificate of the planted structure (if any) is forgotten as the new
+    /// edge could invalidate it.
+    pub fn add_edge(&mut self, src: Vertex, dst: Vertex, weight: W) -> Result<bool, String> {
+        for v in [src, dst].iter() {
+            if v.id < 1 || v.id > self.n as isize {
+                return Err(format!("{} is not a vertex of the graph", v.id));
+            }
+        }
+        let edge = Edge{src, dst};
+        if edge.is_self_loop() && !self.meta.self_loops {
+            return Err(format!("the self loop on {} is not allowed", src.id));
+        }
+        if self.stored(edge).is_some() {
+            return Ok(false);
+        }
+        self.list.insert(edge, weight);
+        self.adj = OnceLock::new();
+        self.certificate = None;
+        Ok(true)
+    }
+    /// Removes the edge `src -> dst` (in either orientation for an undirected
+    /// graph, along with its attributes) and returns its weight, if it
+    /// belonged to the graph
+    pub fn remove_edge(&mut self, src: Vertex, dst: Vertex) -> Option<W> {
+        let edge = self.stored(Edge{src, dst})?;
+        self.adj = OnceLock::new();
+        for values in self.attrs.values_mut() {
+            values.remove(&edge);
+        }
+        self.list.remove(&edge)
+    }
+    /// Changes the weight of the edge `src -> dst` and returns its previous
+    /// weight. Nothing happens when the edge does not belong to the graph.
+    pub fn set_weight(&mut self, src: Vertex, dst: Vertex, weight: W) -> Option<W> {
+        let edge = self.stored(Edge{src, dst})?;
+        self.list.insert(edge, weight)
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the graph6 and sparse6 encodings used by the
+//! nauty/geng tools (canonical labelling, isomorphism filtering, ...).
+//!
+//! Both encodings deal with undirected graphs only. Hence, the arcs of a
+//! digraph are written as undirected edges. graph6 cannot represent self
+//! loops which are then dropped; sparse6 keeps them.
+
+use crate::Graph;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+impl<T> Graph<T> {
+    /// Returns the (deduplicated) undirected edges as (max, min) pairs of
+    /// zero based vertex ids, sorted on the largest end first.
+    fn undirected_pairs(&self) -> BTreeSet<(usize, usize)> {
+        self.list.keys()
+            .map(|e| (e.src.id as usize - 1, e.dst.id as usize - 1))
+            .map(|(u, v)| (u.max(v), u.min(v)))
+            .collect()
+    }
+
+    /// Writes the graph in the graph6 encoding (without the optional
+    /// `>>graph6<<` header)
+    pub fn to_graph6(&self) -> String {
+        let pairs = self.undirected_pairs();
+
+        // the upper triangle of the adjacency matrix, column by column
+        let mut bits = vec![];
+        for j in 1..self.n {
+            for i in 0..j {
+                bits.push(pairs.contains(&(j, i)));
+            }
+        }
+
+        let mut out = size_bytes(self.n);
+        out.extend(pack(&bits, false));
+        out.into_iter().map(char::from).collect()
+    }
+
+    /// Writes the graph6 encoding of the graph as one line of the given
+    /// writer
+    pub fn write_graph6<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{}", self.to_graph6())
+    }
+
+    /// Writes the graph in the sparse6 encoding (without the optional
+    /// `>>sparse6<<` header)
+    pub fn to_sparse6(&self) -> String {
+        let n = self.n;
+        let mut k = 1;
+        while (1_usize << k) < n {
+            k += 1;
+        }
+        let enc = |bits: &mut Vec<bool>, x: usize| {
+            for i in 0..k {
+                bits.push(x & (1 << (k - 1 - i)) != 0);
+            }
+        };
+
+        let mut bits = vec![];
+        let mut curv = 0;
+        for (v, u) in self.undirected_pairs() {
+            if v == curv {
+                bits.push(false);
+                enc(&mut bits, u);
+            } else if v == curv + 1 {
+                curv += 1;
+                bits.push(true);
+                enc(&mut bits, u);
+            } else {
+                curv = v;
+                bits.push(true);
+                enc(&mut bits, v);
+                bits.push(false);
+                enc(&mut bits, u);
+            }
+        }
+        // when padding with ones could be mistaken for an extra edge
+        // (towards vertex n-1), a zero bit must be added first.
+        let padding = (6 - bits.len() % 6) % 6;
+        if k < 6 && n == (1 << k) && padding >= k && curv + 1 < n {
+            bits.push(false);
+        }
+
+        let mut out = vec![b':'];
+        out.extend(size_bytes(n));
+        out.extend(pack(&bits, true));
+        out.into_iter().map(char::from).collect()
+    }
+
+    /// Writes the sparse6 encoding of the graph as one line of the given
+    /// writer
+    pub fn write_sparse6<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{}", self.to_sparse6())
+    }
+}
+
+/// Encodes the number of vertices `n` as the N(n) of the nauty formats
+fn size_bytes(n: usize) -> Vec<u8> {
+    let sixes = |x: usize, count: usize| (0..count).rev()
+        .map(|i| ((x >> (6 * i)) & 0x3f) as u8 + 63)
+        .collect::<Vec<u8>>();
+
+    if n <= 62 {
+        vec![n as u8 + 63]
+    } else if n <= 258_047 {
+        let mut out = vec![126];
+        out.extend(sixes(n, 3));
+        out
+    } else {
+        let mut out = vec![126, 126];
+        out.extend(sixes(n, 6));
+        out
+    }
+}
+
+/// Packs the given bits into printable bytes of 6 bits each (big endian),
+/// the last group being padded with the given bit value.
+fn pack(bits: &[bool], pad: bool) -> Vec<u8> {
+    bits.chunks(6)
+        .map(|chunk| {
+            let mut byte = 0_u8;
+            for i in 0..6 {
+                let bit = chunk.get(i).copied().unwrap_or(pad);
+                byte = (byte << 1) | bit as u8;
+            }
+            byte + 63
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Graph, GraphMeta, Vertex};
+
+    /// Returns the graph with `n` vertices and the given (zero based) edges
+    fn graph(meta: GraphMeta, n: usize, edges: &[(isize, isize)]) -> Graph {
+        let mut g = Graph::empty(meta, n);
+        for (u, v) in edges.iter() {
+            g.add_edge(Vertex{id: u + 1}, Vertex{id: v + 1}, 1).unwrap();
+        }
+        g
+    }
+    fn simple(n: usize, edges: &[(isize, isize)]) -> Graph {
+        graph(GraphMeta::new("test"), n, edges)
+    }
+
+    // the expected encodings are those of networkx
+
+    #[test]
+    fn graph6_of_known_graphs() {
+        assert_eq!("?",  simple(0, &[]).to_graph6());
+        assert_eq!("C~", simple(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]).to_graph6());
+        assert_eq!("DhC", simple(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]).to_graph6());
+        let petersen = [(0, 1), (0, 4), (0, 5), (1, 2), (1, 6), (2, 3), (2, 7), (3, 4), (3, 8), (4, 9),
+                        (5, 7), (5, 8), (6, 8), (6, 9), (7, 9)];
+        assert_eq!("IheA@GUAo", simple(10, &petersen).to_graph6());
+    }
+
+    #[test]
+    fn graph6_of_a_large_graph_uses_a_four_bytes_size() {
+        assert!(simple(63, &[(0, 62)]).to_graph6().starts_with("~??~"));
+    }
+
+    #[test]
+    fn sparse6_of_known_graphs() {
+        assert_eq!(":?", simple(0, &[]).to_sparse6());
+        assert_eq!(":CcKI", simple(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]).to_sparse6());
+        assert_eq!(":DaYn", simple(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]).to_sparse6());
+        assert_eq!(":~??~~?N", simple(63, &[(0, 62)]).to_sparse6());
+    }
+
+    #[test]
+    fn sparse6_keeps_the_self_loops() {
+        let g = graph(GraphMeta::new("test").with_self_loops(), 5, &[(0, 0), (1, 3), (2, 4)]);
+        assert_eq!(":DApj", g.to_sparse6());
+    }
+
+    #[test]
+    fn sparse6_padding_is_not_mistaken_for_an_edge() {
+        // padding `100` with ones would read as an extra edge towards 3
+        assert_eq!(":Cb", simple(4, &[(0, 1)]).to_sparse6());
+        assert_eq!(":Cw", simple(4, &[(0, 3)]).to_sparse6());
+    }
+}
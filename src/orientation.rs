@@ -0,0 +1,106 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module converts between the directed and undirected views of the
+//! same topology: the arcs of a digraph can be symmetrized, and the edges of
+//! an undirected graph can be given an (arbitrary or acyclic) orientation.
+
+use crate::{Edge, Graph, GraphMeta, MergePolicy, Vertex};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::ops::Add;
+
+impl<W: Clone + PartialOrd + Add<Output=W>> Graph<W> {
+    /// Returns the undirected version of the graph: each arc becomes an edge
+    /// and the weights of anti-parallel arcs (which yield the same edge) are
+    /// merged after the given policy. The self loops are kept.
+    pub fn to_undirected(&self, policy: MergePolicy) -> Graph<W> {
+        let mut meta  = self.derived_meta("symmetrization");
+        meta.digraph  = false;
+        meta.oriented = false;
+        self.merged(meta, policy, true)
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    /// Returns the digraph with the given metadata where each edge `e` of
+    /// this graph becomes the arc `orient(e)` (along with its weight and
+    /// attributes). The self loops are only kept when `keep_loops` is set.
+    fn oriented<F>(&self, meta: GraphMeta, keep_loops: bool, orient: F) -> Graph<W>
+        where F: Fn(Edge) -> Edge
+    {
+        let mut g = Graph::empty(meta, self.n);
+        g.labels  = self.labels.clone();
+        for (e, w) in self.list.iter().filter(|(e, _)| keep_loops || !e.is_self_loop()) {
+            let arc = orient(*e);
+            g.list.insert(arc, w.clone());
+            for (name, values) in self.attrs.iter() {
+                if let Some(value) = values.get(e) {
+                    g.attrs.entry(name.clone()).or_default().insert(arc, *value);
+                }
+            }
+        }
+        g
+    }
+    /// Returns an error if the graph already is a digraph
+    fn check_undirected(&self) -> Result<(), String> {
+        if self.meta.digraph {
+            Err("only an undirected graph can be oriented".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the digraph where each edge of this undirected graph is given
+    /// an orientation picked uniformly at random. An error is returned when
+    /// the graph already is a digraph.
+    pub fn orient_randomly<R: Rng>(&self, rng: &mut R) -> Result<Graph<W>, String> {
+        self.check_undirected()?;
+        let mut meta  = self.derived_meta("random orientation");
+        meta.digraph  = true;
+        meta.oriented = true;
+
+        let mut edges = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        let flips = edges.into_iter().map(|e| (e, rng.gen_bool(0.5))).collect::<HashMap<Edge, bool>>();
+        Ok(self.oriented(meta, true, |e| if flips[&e] { e.rev() } else { e }))
+    }
+    /// Returns the directed acyclic graph where each edge of this undirected
+    /// graph is oriented after a topological order of the vertices picked
+    /// uniformly at random. The self loops (which are cycles) are dropped.
+    /// An error is returned when the graph already is a digraph.
+    pub fn orient_acyclically<R: Rng>(&self, rng: &mut R) -> Result<Graph<W>, String> {
+        self.check_undirected()?;
+        let mut meta    = self.derived_meta("acyclic orientation");
+        meta.digraph    = true;
+        meta.oriented   = true;
+        meta.self_loops = false;
+
+        let mut order = self.vertices().collect::<Vec<Vertex>>();
+        order.shuffle(rng);
+        let mut rank  = vec![0; self.n];
+        for (i, v) in order.iter().enumerate() {
+            rank[v.id as usize - 1] = i;
+        }
+        let rank = |v: Vertex| rank[v.id as usize - 1];
+        Ok(self.oriented(meta, false, |e| if rank(e.src) < rank(e.dst) { e } else { e.rev() }))
+    }
+}
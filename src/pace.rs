@@ -0,0 +1,297 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the formats of the PACE challenges (the
+//! `.gr` edge list of the treewidth tracks and the METIS-like successor
+//! lists of the directed feedback vertex set track) and provides generators
+//! producing instances whose optimum is known (or bounded) by construction,
+//! so that they come with certified bounds.
+
+use crate::{Edge, Graph, GraphGenError, GraphMeta, Vertex};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::seq::SliceRandom;
+use std::io::{self, Write};
+
+impl Graph {
+    /// Writes the graph in the `.gr` format of the PACE treewidth (and
+    /// vertex cover) tracks: a `p <problem> <n> <m>` header followed by one
+    /// `<src> <dst>` line per edge. The given comments are written (prefixed
+    /// with `c`) before the header.
+    pub fn to_pace(&self, problem: &str, comments: &[String]) -> String {
+        crate::written(|out| self.write_pace(out, problem, comments))
+    }
+
+    /// Same as `to_pace` but streams the output to the given writer
+    pub fn write_pace<W: Write>(&self, mut out: W, problem: &str, comments: &[String]) -> io::Result<()> {
+        for comment in comments.iter() {
+            writeln!(out, "c {}", comment)?;
+        }
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p {} {} {}", problem, self.n, self.list.len())?;
+
+        let mut edges = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        for edge in edges {
+            writeln!(out, "{} {}", edge.src.id, edge.dst.id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the digraph in the format of the PACE 2022 directed feedback
+    /// vertex set track: a `<n> <m> 0` header followed by one line per vertex
+    /// listing its successors (the line of a vertex without successor is
+    /// empty). The given comments are written (prefixed with `%`) before the
+    /// header.
+    pub fn to_pace_dfvs(&self, comments: &[String]) -> String {
+        crate::written(|out| self.write_pace_dfvs(out, comments))
+    }
+
+    /// Same as `to_pace_dfvs` but streams the output to the given writer
+    pub fn write_pace_dfvs<W: Write>(&self, mut out: W, comments: &[String]) -> io::Result<()> {
+        for comment in comments.iter() {
+            writeln!(out, "% {}", comment)?;
+        }
+        writeln!(out, "% Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "{} {} 0", self.n, self.list.len())?;
+
+        for v in self.vertices() {
+            let successors = self.out_neighbors(v).iter()
+                .map(|u| u.id.to_string())
+                .collect::<Vec<String>>();
+            writeln!(out, "{}", successors.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A random digraph whose minimum feedback vertex set and minimum feedback
+/// arc set both have size `k` (by construction).
+///
+/// The vertices are laid out in a random order and all arcs go 'forward' in
+/// that order, except for `k` 'backward' arcs. Each of these closes a cycle,
+/// and these cycles are vertex disjoint. Hence, any feedback set needs at
+/// least k elements (one per disjoint cycle). Conversely, every cycle must
+/// traverse a backward arc. So removing the backward arcs (or the source of
+/// each backward arc) leaves a DAG.
+#[derive(Debug, Clone)]
+pub struct PlantedFeedbackSet {
+    g       : Graph,
+    /// The planted backward arcs (this is an optimal feedback arc set)
+    backward: Vec<Edge>
+}
+impl PlantedFeedbackSet {
+    /// Creates a digraph with `n` vertices and `k` planted disjoint cycles
+    /// (k is at most n/2 since each cycle spans n/k vertices). Each other
+    /// forward arc appears with probability p. It panics when the parameters
+    /// are invalid (see `try_new`).
+    pub fn new(n: usize, k: usize, p: f64) -> Self {
+        Self::try_new(n, k, p).expect("invalid planted feedback set parameters")
+    }
+    /// Same as `new` but fails when there is no vertex, when p is not a
+    /// probability or when k exceeds n/2
+    pub fn try_new(n: usize, k: usize, p: f64) -> Result<Self, GraphGenError> {
+        GraphGenError::check(n, p)?;
+        if k > n / 2 {
+            return Err(GraphGenError::Conflict("there cannot be more than n/2 vertex disjoint cycles"));
+        }
+        let mut rng = crate::random::rng();
+        let coin    = Bernoulli::new(p).expect("p is a probability");
+
+        let mut order = (1..=n as isize).collect::<Vec<isize>>();
+        order.shuffle(&mut rng);
+
+        let mut g = Graph::empty(GraphMeta::new(format!("digraph with {} planted vertex disjoint cycles (p = {}){}", k, p, crate::random::seed_suffix(crate::random::thread_seed()))).digraph(), n);
+        for i in 0..n {
+            for j in i+1..n {
+                if coin.sample(&mut rng) {
+                    g.list.insert(Edge{src: Vertex{id: order[i]}, dst: Vertex{id: order[j]}}, 1);
+                }
+            }
+        }
+
+        // the disjoint cycles are made of randomly chosen vertices that are
+        // visited in the topological order before the cycle is closed.
+        let mut backward = vec![];
+        if let Some(len) = n.checked_div(k) {
+            let mut picks = (0..n).collect::<Vec<usize>>();
+            picks.shuffle(&mut rng);
+            for members in picks.chunks_exact(len).take(k) {
+                let mut members = members.to_vec();
+                members.sort_unstable();
+                for pair in members.windows(2) {
+                    let src = Vertex{id: order[pair[0]]};
+                    let dst = Vertex{id: order[pair[1]]};
+                    g.list.insert(Edge{src, dst}, 1);
+                }
+                let src = Vertex{id: order[members[len - 1]]};
+                let dst = Vertex{id: order[members[0]]};
+                let arc = Edge{src, dst};
+                g.list.insert(arc, 1);
+                backward.push(arc);
+            }
+        }
+
+        Ok(PlantedFeedbackSet{g, backward})
+    }
+
+    /// Returns the digraph of the benchmark
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+    /// Returns an optimal feedback arc set
+    pub fn feedback_arcs(&self) -> &[Edge] {
+        &self.backward
+    }
+    /// Returns an optimal feedback vertex set
+    pub fn feedback_vertices(&self) -> Vec<Vertex> {
+        self.backward.iter().map(|e| e.src).collect()
+    }
+
+    /// Writes the benchmark in the format of the PACE 2022 directed feedback
+    /// vertex set track. The optimal feedback vertex and arc sets are given
+    /// in the header comments.
+    pub fn to_pace(&self) -> String {
+        crate::written(|out| self.write_pace(out))
+    }
+
+    /// Same as `to_pace` but streams the output to the given writer
+    pub fn write_pace<W: Write>(&self, out: W) -> io::Result<()> {
+        let k        = self.backward.len();
+        let vertices = self.feedback_vertices().iter()
+            .map(|v| v.id.to_string())
+            .collect::<Vec<String>>();
+        let arcs     = self.backward.iter()
+            .map(|e| format!("({},{})", e.src.id, e.dst.id))
+            .collect::<Vec<String>>();
+
+        let comments = vec![
+            format!("Pseudo-random {}", self.g.meta.provenance),
+            format!("The minimum feedback vertex set and feedback arc set both have size {} (by construction)", k),
+            format!("Optimal feedback vertex set: {}", vertices.join(" ")),
+            format!("Optimal feedback arc set: {}", arcs.join(" ")),
+            "-------------------------------------------------------------".to_string(),
+        ];
+        self.g.write_pace_dfvs(out, &comments)
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
+
+/// A random partial k-tree: a random subgraph of a random k-tree. Since the
+/// treewidth of a k-tree is k and the treewidth never increases when edges
+/// are removed, the treewidth of the graph is at most k.
+#[derive(Debug, Clone)]
+pub struct PartialKTree {
+    g: Graph,
+    k: usize
+}
+impl PartialKTree {
+    /// Creates a partial k-tree with `n` vertices. The underlying k-tree
+    /// starts from a (k+1)-clique; each other vertex is then connected to all
+    /// members of a random k-clique of the graph built so far. Finally, each
+    /// edge of the k-tree is kept with probability p. It panics when the
+    /// parameters are invalid (see `try_new`).
+    pub fn new(n: usize, k: usize, p: f64) -> Self {
+        Self::try_new(n, k, p).expect("invalid partial k-tree parameters")
+    }
+    /// Same as `new` but fails when there is no vertex or when p is not a
+    /// probability
+    pub fn try_new(n: usize, k: usize, p: f64) -> Result<Self, GraphGenError> {
+        GraphGenError::check(n, p)?;
+        let mut rng = crate::random::rng();
+        let coin    = Bernoulli::new(p).expect("p is a probability");
+
+        let mut ids = (1..=n as isize).collect::<Vec<isize>>();
+        ids.shuffle(&mut rng);
+
+        let mut edges = vec![];
+        let base      = (k + 1).min(n);
+        for i in 0..base {
+            for j in i+1..base {
+                edges.push((ids[i], ids[j]));
+            }
+        }
+
+        let mut cliques = vec![];
+        if base == k + 1 {
+            for skip in 0..base {
+                cliques.push(ids[..base].iter().enumerate()
+                    .filter(|(i, _)| *i != skip)
+                    .map(|(_, v)| *v)
+                    .collect::<Vec<isize>>());
+            }
+        }
+        for v in ids.iter().skip(base) {
+            let clique = cliques.choose(&mut rng).cloned().unwrap_or_default();
+            for u in clique.iter() {
+                edges.push((*u, *v));
+            }
+            for skip in 0..clique.len() {
+                let mut next = clique.clone();
+                next[skip]   = *v;
+                cliques.push(next);
+            }
+        }
+
+        let mut g = Graph::empty(GraphMeta::new(format!("partial {}-tree (each edge of the {}-tree kept with p = {}){}", k, k, p, crate::random::seed_suffix(crate::random::thread_seed()))), n);
+        for (u, v) in edges {
+            if coin.sample(&mut rng) {
+                g.list.insert(Edge{src: Vertex{id: u}, dst: Vertex{id: v}}, 1);
+            }
+        }
+
+        Ok(PartialKTree{g, k})
+    }
+
+    /// Returns the graph of the benchmark
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+
+    /// Writes the graph in the PACE treewidth `.gr` format. The upper bound
+    /// on the treewidth is given in the header comments.
+    pub fn to_pace(&self) -> String {
+        crate::written(|out| self.write_pace(out))
+    }
+
+    /// Same as `to_pace` but streams the output to the given writer
+    pub fn write_pace<W: Write>(&self, out: W) -> io::Result<()> {
+        let comments = vec![
+            format!("Pseudo-random {}", self.g.meta.provenance),
+            format!("The treewidth of this graph is at most {} (by construction)", self.k),
+            "-------------------------------------------------------------".to_string(),
+        ];
+        self.g.write_pace(out, "tw", &comments)
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the Pajek `.net` format.
+
+use crate::{Graph, Vertex};
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Writes the graph in the Pajek format: a `*Vertices` section listing
+    /// the (labeled) vertices followed by an `*Edges` section (or `*Arcs` for
+    /// a digraph) giving one `<src> <dst> <weight>` line per edge.
+    pub fn to_pajek(&self) -> String {
+        crate::written(|out| self.write_pajek(out))
+    }
+
+    /// Same as `to_pajek` but streams the output to the given writer
+    pub fn write_pajek<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "% Graph from {}", self.meta.provenance)?;
+        writeln!(out, "% Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "*Vertices {}", self.n)?;
+        for v in 1..=self.n {
+            writeln!(out, "{} \"{}\"", v, self.label_or_id(Vertex{id: v as isize}).replace('"', "'"))?;
+        }
+
+        let section = if self.meta.digraph { "*Arcs" } else { "*Edges" };
+        writeln!(out, "{}", section)?;
+        for (edge, w) in self.list.iter() {
+            writeln!(out, "{} {} {}", edge.src.id, edge.dst.id, w)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+
+//! This module samples the G(n, p) graphs on all cores (with the `rayon`
+//! feature). The candidate edges are split in chunks which are skipped
+//! through in parallel (see `ErModel::bernoulli`), each with a random stream
+//! of its own: the streams of a single ChaCha generator are indexed by the
+//! chunks. The chunks only depend on the model (not on the number of
+//! threads), hence the graphs are reproducible from a single seed. Only the
+//! sampling is parallel: the edge map of the graph is filled by a single
+//! thread, while the next chunks are being sampled.
+
+use crate::{Edge, ErGenerator, Graph, Vertex};
+use crate::bernoulli::{skip, skip_range, Rows};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rayon::prelude::*;
+
+impl<R: Rng> ErGenerator<R> {
+    /// The expected number of edges of each chunk of candidate edges
+    const EDGES_PER_CHUNK: f64 = 65536.0;
+
+    /// Generates a random graph on all cores. Only the unconstrained G(n, p)
+    /// models (see `ErModel::bernoulli`) can be generated in parallel: the
+    /// graphs of the other models are generated by `gen`. The graphs
+    /// generated in parallel differ from those which `gen` would have
+    /// generated (with the same seed) but have the same distribution.
+    ///
+    /// The chunks are sampled in waves of one chunk per thread, and the edges
+    /// of each wave are inserted in the graph while the next one is sampled.
+    /// Hence, the insertions (which cannot be parallel) bound the speedup,
+    /// but no more than two waves of edges are held besides the graph.
+    pub fn gen_parallel(&mut self) -> Graph {
+        let model = self.model;
+        if !model.bernoulli || model.has_constraints() {
+            return self.gen();
+        }
+        let key   = self.rng.gen::<u64>();
+        let rows  = Rows::new(model);
+        let total = rows.total();
+        let chunk = (Self::EDGES_PER_CHUNK / model.p.max(f64::MIN_POSITIVE)).clamp(Self::EDGES_PER_CHUNK, u64::MAX as f64) as u128;
+        let count = total.div_ceil(chunk) as u64;
+        let wave  = rayon::current_num_threads().max(1) as u64;
+        let sample = |c: u64| {
+            let mut rng  = ChaCha20Rng::seed_from_u64(key);
+            let mut rows = Rows::new(model);
+            let from     = c as u128 * chunk;
+            let to       = (from + chunk).min(total);
+            rng.set_stream(c);
+            rows.seek(from);
+            let mut edges = vec![];
+            skip_range(&mut rng, from, to, model.p, |rng, i| edges.push(rows.edge(rng, i)));
+            edges
+        };
+
+        let mut g = Graph::empty(model.into(), model.n);
+        let expected = total as f64 * model.p;
+        g.list.reserve((expected + 4.0 * expected.sqrt()) as usize);
+        let mut sampled = vec![];
+        let mut next    = 0;
+        while next < count || !sampled.is_empty() {
+            let upto     = (next + wave).min(count);
+            let inserted = std::mem::take(&mut sampled);
+            let list     = &mut g.list;
+            let ((), edges) = rayon::join(
+                || for edge in inserted.into_iter().flatten() { list.insert(edge, 1); },
+                || (next..upto).into_par_iter().map(sample).collect::<Vec<Vec<Edge>>>());
+            sampled = edges;
+            next    = upto;
+        }
+        if let Some(q) = model.loop_p {
+            skip(&mut self.rng, model.n as u128, q, |_, i| {
+                let v = Vertex{id: i as isize + 1};
+                g.list.insert(Edge{src: v, dst: v}, 1);
+            });
+        }
+        g
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module reads existing graphs, so that the weights, transformations
+//! and writers of this crate can be applied to them. Two formats are
+//! understood: DIMACS graphs and plain edge lists.
+
+use crate::Graph;
+use std::io::BufRead;
+use std::str::FromStr;
+
+impl Graph {
+    /// Parses a graph in the DIMACS format. See `read_dimacs`.
+    pub fn parse_dimacs(text: &str, digraph: bool) -> Result<Graph, String> {
+        Self::read_dimacs(text.as_bytes(), digraph)
+    }
+    /// Reads a graph in the DIMACS format. Both flavors of the format are
+    /// accepted: a `p <kind> <n> <m>` problem line followed by `e <src> <dst>
+    /// [weight]` (or `a ...`) edge lines, or a bare `<n> <m>` header followed
+    /// by `<src> <dst> [weight]` lines (as written by `to_dimacs`). Comments
+    /// (`c`) and vertex lines (`n`) are ignored, and the edges weigh 1 unless
+    /// told otherwise.
+    pub fn read_dimacs<R: BufRead>(input: R, digraph: bool) -> Result<Graph, String> {
+        let mut n     = None;
+        let mut edges = vec![];
+        for (i, line) in input.lines().enumerate() {
+            let line   = line.map_err(|e| e.to_string())?;
+            let tokens = line.split_whitespace().collect::<Vec<&str>>();
+            match tokens.first() {
+                None | Some(&"c") | Some(&"n") => {},
+                Some(&"p") => n = Some(token(&tokens, 2, i)?),
+                Some(&"e") | Some(&"a") => edges.push(edge(&tokens[1..], i)?),
+                Some(_) if n.is_none() => n = Some(token(&tokens, 0, i)?),
+                Some(_) => edges.push(edge(&tokens, i)?)
+            }
+        }
+        let n = n.ok_or_else(|| "the dimacs header is missing".to_string())?;
+        Self::from_weighted_edges(n, digraph, edges)
+    }
+
+    /// Parses a plain edge list. See `read_edge_list`.
+    pub fn parse_edge_list(text: &str, digraph: bool, zero_indexed: bool) -> Result<Graph, String> {
+        Self::read_edge_list(text.as_bytes(), digraph, zero_indexed)
+    }
+    /// Reads a plain edge list: one `<src> <dst> [weight]` line per edge
+    /// where the fields are separated by spaces, tabs or commas (hence the
+    /// csv and snap outputs can be read back). Empty lines, comments (`#`
+    /// and `%`) and a leading header line (e.g. `src,dst,weight`) are
+    /// ignored. The number of vertices is the largest vertex id; which are
+    /// numbered from zero when `zero_indexed` is set (the graph is then
+    /// zero indexed, see `GraphMeta::zero_indexed`).
+    pub fn read_edge_list<R: BufRead>(input: R, digraph: bool, zero_indexed: bool) -> Result<Graph, String> {
+        let shift     = if zero_indexed { 1 } else { 0 };
+        let mut edges = vec![];
+        let mut first = true;
+        for (i, line) in input.lines().enumerate() {
+            let line   = line.map_err(|e| e.to_string())?;
+            let tokens = line.split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|t| !t.is_empty())
+                .collect::<Vec<&str>>();
+            if tokens.is_empty() || tokens[0].starts_with('#') || tokens[0].starts_with('%') {
+                continue;
+            }
+            let is_header = first && tokens[0].parse::<isize>().is_err();
+            first = false;
+            if !is_header {
+                let (src, dst, w) = edge(&tokens, i)?;
+                edges.push((src + shift, dst + shift, w));
+            }
+        }
+        let n = edges.iter().map(|(src, dst, _)| *src.max(dst)).max().unwrap_or(0).max(0);
+        let mut g = Self::from_weighted_edges(n as usize, digraph, edges)?;
+        g.set_zero_indexed(zero_indexed);
+        Ok(g)
+    }
+}
+
+/// Parses the token at the given position of the line `i` (zero based)
+fn token<T: FromStr>(tokens: &[&str], pos: usize, i: usize) -> Result<T, String> {
+    tokens.get(pos)
+        .and_then(|t| t.parse().ok())
+        .ok_or_else(|| format!("line {}: expected a number at position {}", i + 1, pos + 1))
+}
+/// Parses the `<src> <dst> [weight]` edge of the line `i` (zero based)
+fn edge(tokens: &[&str], i: usize) -> Result<(isize, isize, isize), String> {
+    let src = token(tokens, 0, i)?;
+    let dst = token(tokens, 1, i)?;
+    let w   = if tokens.len() > 2 { token(tokens, 2, i)? } else { 1 };
+    Ok((src, dst, w))
+}
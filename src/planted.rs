@@ -0,0 +1,193 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module lets the Erdos-Renyi model plant structures of known size in
+//! the generated graphs, and record them as certificates to validate solvers.
+
+use crate::{nb_possible_edges, Certificate, Connectivity, ErGenerator, ErModel, Edge, Graph, GraphGenError, Vertex, Weight};
+use crate::constraints::Sample;
+use rand::Rng;
+use rand::seq::index;
+use std::collections::HashSet;
+use std::ops::Neg;
+
+/// The structure planted in the graphs generated by a model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Planted {
+    /// An independent set of the given number of vertices
+    IndependentSet(usize),
+    /// A vertex cover of the given number of vertices (the other vertices
+    /// form an independent set)
+    VertexCover(usize),
+    /// A bisection of the vertices where the edges inside a side are
+    /// rejected with the given probability (the bias)
+    Cut(f64)
+}
+
+impl ErModel {
+    /// Reserves `k` random vertices as an independent set of the generated
+    /// graphs: no edge is ever picked between two of them. The set is
+    /// recorded as the certificate of the graphs, and bounds the size of
+    /// their maximum independent set from below.
+    pub fn planted_independent_set(self, k: usize) -> Self {
+        ErModel{planted: Some(Planted::IndependentSet(k)), ..self}
+    }
+    /// Reserves `k` random vertices as a vertex cover of the generated
+    /// graphs: each edge touches at least one of them (the other vertices
+    /// form an independent set). The cover is recorded as the certificate of
+    /// the graphs, and bounds the size of their minimum vertex cover from
+    /// above.
+    pub fn planted_vertex_cover(self, k: usize) -> Self {
+        ErModel{planted: Some(Planted::VertexCover(k)), ..self}
+    }
+    /// Splits the vertices of the generated graphs in two random halves, and
+    /// rejects the edges inside a half with probability `bias` (a bias of 1
+    /// makes the graphs bipartite). The cut between the halves is recorded as
+    /// the certificate of the graphs: once the weights of the edges it cuts
+    /// are made positive and the others negative (see `sign_planted_cut`),
+    /// it is a maximum cut of known value.
+    pub fn planted_cut(self, bias: f64) -> Self {
+        ErModel{planted: Some(Planted::Cut(bias)), ..self}
+    }
+    /// Checks that the planted structure fits in the graphs: the planted set
+    /// cannot have more vertices than the graph, the bias of the cut must be
+    /// a probability, and the edges these forbid must leave room for the
+    /// wanted edges and let the graph be connected.
+    pub(crate) fn validate_planted(self, planted: Planted, wanted: u128, possible: u128) -> Result<(), GraphGenError> {
+        let cycle = self.connectivity == Some(Connectivity::HamiltonianCycle) && self.n > 2;
+        match planted {
+            Planted::IndependentSet(k) | Planted::VertexCover(k) => {
+                if k > self.n {
+                    return Err(GraphGenError::Conflict("the planted set cannot have more vertices than the graph"));
+                }
+                let k        = if let Planted::VertexCover(_) = planted { self.n - k } else { k };
+                let possible = possible - nb_possible_edges(k, self.has_antiparallel_arcs(), self.picks_loops());
+                if wanted > possible {
+                    return Err(GraphGenError::TooManyEdges{wanted, possible});
+                }
+                if cycle && 2 * k > self.n {
+                    return Err(GraphGenError::Conflict("a Hamiltonian cycle cannot go through an independent set of more than n/2 vertices"));
+                }
+                if self.connectivity.is_some() && self.n > 1 && k == self.n {
+                    return Err(GraphGenError::Conflict("the vertices of an independent set cannot be connected"));
+                }
+            },
+            Planted::Cut(bias) => {
+                if !(0.0..=1.0).contains(&bias) {
+                    return Err(GraphGenError::InvalidProbability(bias));
+                }
+                if bias == 1.0 {
+                    let half     = (self.n / 2) as u128;
+                    let across   = half * (self.n as u128 - half);
+                    let possible = if self.has_antiparallel_arcs() { 2 * across } else { across };
+                    if wanted > possible {
+                        return Err(GraphGenError::TooManyEdges{wanted, possible});
+                    }
+                    if cycle && self.n % 2 == 1 {
+                        return Err(GraphGenError::Conflict("a Hamiltonian cycle cannot alternate between the sides of a bipartite graph with an odd number of vertices"));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Rng> ErGenerator<R> {
+    /// Picks the random vertices of the planted structure in the (empty)
+    /// graph, and marks those which no edge may join
+    pub(crate) fn plant(&mut self, s: &mut Sample, planted: Planted) {
+        let n = self.model.n;
+        let (k, bipartite) = match planted {
+            Planted::IndependentSet(k) => (k, false),
+            Planted::VertexCover(k)    => (n - k, false),
+            Planted::Cut(bias)         => (n / 2, bias >= 1.0)
+        };
+        let mut picked = vec![false; n];
+        for i in index::sample(&mut self.rng, n, k).into_iter() {
+            picked[i] = true;
+        }
+        let mut set = vec![];
+        for v in s.graph.vertices() {
+            let i = v.id as usize - 1;
+            match planted {
+                Planted::IndependentSet(_) if picked[i] => { s.set_independent(v); set.push(v); },
+                // the cover is made of the vertices outside the independent set
+                Planted::VertexCover(_) if picked[i]    => s.set_independent(v),
+                Planted::VertexCover(_)                 => set.push(v),
+                Planted::Cut(_) if picked[i]            => { s.set_side(v); set.push(v); },
+                _ => ()
+            }
+        }
+        if bipartite {
+            s.set_bipartite();
+        }
+        s.graph.certificate = Some(match planted {
+            Planted::IndependentSet(_) => Certificate::IndependentSet(set),
+            Planted::VertexCover(_)    => Certificate::VertexCover(set),
+            Planted::Cut(_)            => Certificate::Cut(set)
+        });
+    }
+    /// Returns true iff the sampled edge is kept despite the bias of the
+    /// planted cut: an edge inside a side is rejected with that probability
+    pub(crate) fn keeps(&mut self, s: &Sample, edge: Edge) -> bool {
+        match self.model.planted {
+            Some(Planted::Cut(bias)) if s.side(edge.src) == s.side(edge.dst) => !self.rng.gen_bool(bias),
+            _ => true
+        }
+    }
+}
+
+impl<W: Weight + Neg<Output=W>> Graph<W> {
+    /// Makes the weights of the edges which the planted cut (if any) cuts
+    /// positive and those of the other edges negative. The planted cut then
+    /// is a maximum cut: it gets all the positive weights and none of the
+    /// negative ones.
+    pub fn sign_planted_cut(&mut self) {
+        let side = match self.certificate.as_ref() {
+            Some(Certificate::Cut(side)) => side.iter().copied().collect::<HashSet<Vertex>>(),
+            _ => return
+        };
+        for (edge, w) in self.list.iter_mut() {
+            let cut      = side.contains(&edge.src) != side.contains(&edge.dst);
+            let negative = w.to_f64() < 0.0;
+            if cut == negative {
+                *w = -*w;
+            }
+        }
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Returns the value of the planted cut (if any): the total weight of
+    /// the edges it cuts
+    pub fn planted_cut_value(&self) -> Option<f64> {
+        match self.certificate.as_ref() {
+            Some(Certificate::Cut(side)) => {
+                let side = side.iter().copied().collect::<HashSet<Vertex>>();
+                Some(self.list.iter()
+                    .filter(|(e, _)| side.contains(&e.src) != side.contains(&e.dst))
+                    .map(|(_, w)| w.to_f64())
+                    .sum())
+            },
+            _ => None
+        }
+    }
+}
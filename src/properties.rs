@@ -0,0 +1,101 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+
+//! This module checks the structural properties which the acceptance tests
+//! of the generated instances commonly ask for: connectivity, bipartiteness
+//! and acyclicity.
+
+use crate::Graph;
+use std::collections::VecDeque;
+
+impl<W> Graph<W> {
+    /// Returns true iff each vertex can be reached from any other one (the
+    /// orientation of the arcs of a digraph is ignored: this is weak
+    /// connectivity)
+    pub fn is_connected(&self) -> bool {
+        if self.n == 0 {
+            return true;
+        }
+        let mut visited = vec![false; self.n];
+        let mut stack   = vec![self.vertices().next().expect("the graph has a vertex")];
+        let mut reached = 1;
+        visited[0] = true;
+        while let Some(v) = stack.pop() {
+            for u in self.neighbors(v) {
+                if !visited[u.id as usize - 1] {
+                    visited[u.id as usize - 1] = true;
+                    reached += 1;
+                    stack.push(*u);
+                }
+            }
+        }
+        reached == self.n
+    }
+    /// Returns true iff the vertices can be split in two sides such that
+    /// each edge joins both sides (the orientation of the arcs of a digraph
+    /// is ignored). This tries to 2-color each component with a breadth
+    /// first search, and fails as soon as an edge joins two vertices of the
+    /// same color (e.g. a self loop).
+    pub fn is_bipartite(&self) -> bool {
+        let mut color = vec![None; self.n];
+        for start in self.vertices() {
+            if color[start.id as usize - 1].is_some() {
+                continue;
+            }
+            color[start.id as usize - 1] = Some(false);
+            let mut queue = VecDeque::new();
+            queue.push_back(start);
+            while let Some(v) = queue.pop_front() {
+                let side = color[v.id as usize - 1].expect("a queued vertex is colored");
+                for u in self.neighbors(v) {
+                    match color[u.id as usize - 1] {
+                        None                  => { color[u.id as usize - 1] = Some(!side); queue.push_back(*u); },
+                        Some(s) if s == side  => return false,
+                        Some(_)               => ()
+                    }
+                }
+            }
+        }
+        true
+    }
+    /// Returns true iff the graph is a directed acyclic graph: its vertices
+    /// can be sorted so that each arc goes forward (this is Kahn's
+    /// topological sort). An undirected graph is only acyclic in that sense
+    /// when it has no edge, as each edge can be travelled both ways.
+    pub fn is_dag(&self) -> bool {
+        if !self.meta.digraph {
+            return self.list.is_empty();
+        }
+        let mut in_degree = self.vertices().map(|v| self.in_degree(v)).collect::<Vec<usize>>();
+        let mut ready     = self.vertices().filter(|v| in_degree[v.id as usize - 1] == 0).collect::<Vec<_>>();
+        let mut sorted    = 0;
+        while let Some(v) = ready.pop() {
+            sorted += 1;
+            for u in self.out_neighbors(v) {
+                in_degree[u.id as usize - 1] -= 1;
+                if in_degree[u.id as usize - 1] == 0 {
+                    ready.push(*u);
+                }
+            }
+        }
+        sorted == self.n
+    }
+}
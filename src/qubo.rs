@@ -0,0 +1,164 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module derives QUBO (and Ising) formulations of the max-cut problem
+//! from a weighted random graph. These are the formats expected by quantum
+//! annealers and QAOA simulators.
+
+use crate::Graph;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// The max-cut problem of a weighted graph, formulated as a quadratic
+/// unconstrained binary optimization (or equivalently as an Ising model).
+///
+/// With `x_i ∈ {0, 1}` telling on which side of the cut vertex `i` lies, the
+/// value of the cut is `sum w_ij (x_i + x_j - 2 x_i x_j)`. Minimizing the
+/// QUBO `x^T Q x` hence yields a maximum cut when `Q_ii = -sum_j w_ij` and
+/// `Q_ij = 2 w_ij` (upper triangular form).
+///
+/// With spins `s_i ∈ {-1, 1}`, the value of the cut is
+/// `(W - sum w_ij s_i s_j) / 2` where `W` is the total edge weight. So the
+/// Ising formulation simply uses couplers `J_ij = w_ij` and no field.
+#[derive(Debug, Clone)]
+pub struct MaxCutQubo {
+    g    : Graph,
+    ising: bool
+}
+impl MaxCutQubo {
+    pub fn new(g: Graph) -> Self {
+        MaxCutQubo{g, ising: false}
+    }
+    /// Produce the Ising coefficients rather than the QUBO matrix
+    pub fn ising(self) -> Self {
+        MaxCutQubo{g: self.g, ising: true}
+    }
+
+    /// Returns the graph underlying this instance
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+
+    /// Returns the (undirected) weight of each pair of distinct endpoints.
+    /// Self loops are never cut, hence they are ignored. In a digraph, the
+    /// weights of both orientations are summed.
+    fn couplers(&self) -> BTreeMap<(isize, isize), isize> {
+        let mut couplers = BTreeMap::new();
+        for (edge, w) in self.g.list.iter() {
+            if edge.is_self_loop() {
+                continue;
+            }
+            let a = edge.src.id.min(edge.dst.id);
+            let b = edge.src.id.max(edge.dst.id);
+            *couplers.entry((a, b)).or_insert(0) += *w;
+        }
+        couplers
+    }
+
+    fn header<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let gtype = if self.g.meta.digraph { "digraph" } else {"graph"};
+        writeln!(out, "c Pseudo-random max-cut instance on a {} from {}", gtype, self.g.meta.provenance)?;
+        writeln!(out, "c The graph has {} vertices and {} edges", self.g.n, self.g.list.len())?;
+        self.g.write_certificate(out, "c")?;
+        writeln!(out, "c -------------------------------------------------------------")
+    }
+
+    /// Writes the QUBO matrix in the (qbsolv) `.qubo` format. The nodes are
+    /// numbered from zero.
+    pub fn to_qubo(&self) -> String {
+        crate::written(|out| self.write_qubo(out))
+    }
+
+    /// Same as `to_qubo` but streams the output to the given writer
+    pub fn write_qubo<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let couplers  = self.couplers();
+        let mut diag  = vec![0; self.g.n];
+        for ((a, b), w) in couplers.iter() {
+            diag[*a as usize - 1] -= w;
+            diag[*b as usize - 1] -= w;
+        }
+
+        self.header(&mut out)?;
+        writeln!(out, "c Minimizing x^T Q x yields a maximum cut")?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p qubo 0 {} {} {}", self.g.n, self.g.n, couplers.len())?;
+
+        writeln!(out, "c Diagonal terms")?;
+        for (i, q) in diag.iter().enumerate() {
+            writeln!(out, "{} {} {}", i, i, q)?;
+        }
+        writeln!(out, "c Off diagonal (coupler) terms")?;
+        for ((a, b), w) in couplers.iter() {
+            writeln!(out, "{} {} {}", a - 1, b - 1, 2 * w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the Ising coefficients: one line `i j J_ij` per coupler. The
+    /// fields `h_i` are all zero and the nodes are numbered from zero.
+    pub fn to_ising(&self) -> String {
+        crate::written(|out| self.write_ising(out))
+    }
+
+    /// Same as `to_ising` but streams the output to the given writer
+    pub fn write_ising<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let couplers = self.couplers();
+        let total    = couplers.values().sum::<isize>();
+
+        self.header(&mut out)?;
+        writeln!(out, "c Minimizing E(s) = sum J_ij s_i s_j yields a maximum cut")?;
+        writeln!(out, "c The value of the cut is ({} - E(s)) / 2", total)?;
+        writeln!(out, "c All fields h_i are zero")?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+        writeln!(out, "p ising {} {}", self.g.n, couplers.len())?;
+
+        for ((a, b), w) in couplers.iter() {
+            writeln!(out, "{} {} {}", a - 1, b - 1, w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the QUBO or Ising formulation depending on the configuration
+    pub fn to_text(&self) -> String {
+        if self.ising {
+            self.to_ising()
+        } else {
+            self.to_qubo()
+        }
+    }
+    pub fn write_text<W: Write>(&self, out: W) -> io::Result<()> {
+        if self.ising {
+            self.write_ising(out)
+        } else {
+            self.write_qubo(out)
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.g.to_dot()
+    }
+    pub fn write_dot<W: Write>(&self, out: W) -> io::Result<()> {
+        self.g.write_dot(out)
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module provides the source of randomness of all the generators. By
+//! default, it is seeded from the entropy of the system; but it can be
+//! seeded explicitly so as to make the generation reproducible. The weights
+//! can be drawn from a source of their own, so that the topology can be held
+//! fixed while the weights vary (and vice versa).
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    /// The seed given to the current thread (if any)
+    static SEED: Cell<Option<u64>> = const { Cell::new(None) };
+    /// The rng from which all others are derived once a seed has been given
+    static SEEDED: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+    /// The rng from which the weight rngs are derived once a weight seed has
+    /// been given
+    static WEIGHTS: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Seeds the source of randomness of the current thread: all the random
+/// choices it subsequently makes (generated graphs, weights, perturbations,
+/// ...) are then fully determined by the given seed.
+pub fn seed(seed: u64) {
+    SEED.with(|s| s.set(Some(seed)));
+    SEEDED.with(|rng| *rng.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
+}
+
+/// Returns the seed given to the current thread (if any). The generators
+/// which draw from `rng()` record it in their output so that their instances
+/// can be regenerated.
+pub(crate) fn thread_seed() -> Option<u64> {
+    SEED.with(|s| s.get())
+}
+
+/// Returns ` w/ seed <seed>` (or nothing when there is no seed), which is how
+/// the seed is appended to the description of an instance
+pub(crate) fn seed_suffix(seed: Option<u64>) -> String {
+    seed.map_or_else(String::new, |seed| format!(" w/ seed {}", seed))
+}
+
+/// Returns a new rng. It is derived from the seeded rng of the current thread
+/// when there is one, and seeded from the system entropy otherwise. This is
+/// the rng to hand to the operations taking one (e.g. `shuffle_labels`) in
+/// order to keep them reproducible.
+pub fn rng() -> StdRng {
+    SEEDED.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(seeded) => StdRng::from_rng(seeded).expect("a seeded rng never fails"),
+        None         => StdRng::from_entropy()
+    })
+}
+
+/// Seeds the source of randomness of the weights (of the edges and vertices,
+/// and of the edge attributes) of the current thread independently of that
+/// of the topology
+pub fn seed_weights(seed: u64) {
+    WEIGHTS.with(|rng| *rng.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
+}
+
+/// Returns a new rng to draw weights from. It is derived from the weight
+/// seed when one was given, and is the same as `rng()` otherwise.
+pub fn weight_rng() -> StdRng {
+    WEIGHTS.with(|weights| match weights.borrow_mut().as_mut() {
+        Some(seeded) => StdRng::from_rng(seeded).expect("a seeded rng never fails"),
+        None         => rng()
+    })
+}
@@ -0,0 +1,117 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module perturbs the graphs by rewiring their edges while preserving
+//! their degree sequence. Rewiring a growing fraction of the edges of a base
+//! graph yields a family of progressively noisier copies of that graph.
+
+use crate::{Edge, Graph};
+use rand::Rng;
+use std::sync::OnceLock;
+
+impl<W: Clone> Graph<W> {
+    /// Moves the edge `from` (along with its weight and attributes) so that
+    /// it becomes the edge `to`
+    fn move_edge(&mut self, from: Edge, to: Edge) {
+        let weight = self.list.remove(&from).expect("the edge belongs to the graph");
+        self.list.insert(to, weight);
+        for values in self.attrs.values_mut() {
+            if let Some(value) = values.remove(&from) {
+                values.insert(to, value);
+            }
+        }
+    }
+
+    /// Returns a copy of the graph where a `fraction` of the edges has been
+    /// rewired using double edge swaps: two edges `a -> b` and `c -> d` are
+    /// replaced by `a -> d` and `c -> b` (each one keeping the weight and
+    /// attributes of the edge it replaces). This preserves the degree of
+    /// each vertex (the in and out degrees in a digraph). The swaps never
+    /// create self loops nor duplicate edges, and the self loops are never
+    /// rewired. Fewer edges are rewired when the graph does not allow for
+    /// enough swaps. The certificate of the planted structure (if any) is
+    /// forgotten as soon as an edge is rewired since the swaps could
+    /// invalidate it. An error is returned when the fraction is not in
+    /// [0, 1].
+    pub fn rewire<R: Rng>(&self, fraction: f64, rng: &mut R) -> Result<Graph<W>, String> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(format!("the fraction of rewired edges ({}) must be in [0, 1]", fraction));
+        }
+        let mut g     = self.clone();
+        let mut edges = g.list.keys().filter(|e| !e.is_self_loop()).copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+
+        // each swap rewires two edges
+        let swaps    = (fraction * edges.len() as f64 / 2.0).round() as usize;
+        let attempts = 100 * swaps;
+        let mut done = 0;
+        for _ in 0..attempts {
+            if done == swaps {
+                break;
+            }
+            let i = rng.gen_range(0, edges.len());
+            let j = rng.gen_range(0, edges.len());
+            if i == j {
+                continue;
+            }
+            let first      = edges[i];
+            let mut second = edges[j];
+            // both ways of swapping the ends are possible in undirected graphs
+            if !g.meta.digraph && rng.gen_bool(0.5) {
+                second = second.rev();
+            }
+            let one = Edge{src: first.src,  dst: second.dst};
+            let two = Edge{src: second.src, dst: first.dst};
+            if one.is_self_loop() || two.is_self_loop() || g.stored(one).is_some() || g.stored(two).is_some() {
+                continue;
+            }
+            g.move_edge(first, one);
+            g.move_edge(edges[j], two);
+            edges[i] = one;
+            edges[j] = two;
+            done    += 1;
+        }
+
+        if done > 0 {
+            g.certificate = None;
+        }
+        g.adj  = OnceLock::new();
+        g.meta = self.derived_meta(&format!("rewiring ({} swaps)", done));
+        g.meta.oriented = false;
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErModel;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn rewiring_forgets_the_planted_certificate() {
+        let g = ErModel::new(20, 0.3).planted_independent_set(6).generator_with_seed(3).gen();
+        assert!(g.certificate().is_some());
+        let kept = g.rewire(0.0, &mut StdRng::seed_from_u64(3)).unwrap();
+        assert_eq!(g.certificate(), kept.certificate());
+        let rewired = g.rewire(1.0, &mut StdRng::seed_from_u64(3)).unwrap();
+        assert!(rewired.certificate().is_none());
+    }
+}
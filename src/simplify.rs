@@ -0,0 +1,107 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module simplifies the graphs: it strips their self loops and merges
+//! the edges joining the same pair of vertices (in either orientation), the
+//! weight of the merged edge being obtained after a given policy.
+
+use crate::{Edge, Graph, GraphMeta};
+use std::collections::HashMap;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// How the weights of the edges joining the same vertices are merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// The merged edge weighs the least of the weights
+    Min,
+    /// The merged edge weighs the greatest of the weights
+    Max,
+    /// The merged edge weighs the sum of the weights
+    Sum
+}
+impl MergePolicy {
+    /// Merges the two weights after this policy
+    pub fn merge<W: PartialOrd + Add<Output=W>>(self, a: W, b: W) -> W {
+        match self {
+            MergePolicy::Min => if b < a { b } else { a },
+            MergePolicy::Max => if b > a { b } else { a },
+            MergePolicy::Sum => a + b
+        }
+    }
+}
+impl FromStr for MergePolicy {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Self, Self::Err> {
+        match txt.to_lowercase().as_str() {
+            "min" => Ok(MergePolicy::Min),
+            "max" => Ok(MergePolicy::Max),
+            "sum" => Ok(MergePolicy::Sum),
+            _     => Err(txt.to_owned())
+        }
+    }
+}
+
+impl<W: Clone + PartialOrd + Add<Output=W>> Graph<W> {
+    /// Returns the graph with the given metadata where all edges joining the
+    /// same pair of vertices (in either orientation) are merged after the
+    /// policy. The merged edge keeps the orientation and attributes of the
+    /// first of these edges (in increasing order). The self loops are only
+    /// kept when `keep_loops` is set.
+    pub(crate) fn merged(&self, meta: GraphMeta, policy: MergePolicy, keep_loops: bool) -> Graph<W> {
+        let mut edges = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+
+        let mut g    = Graph::<W>::empty(meta, self.n);
+        g.labels     = self.labels.clone();
+        let mut kept = HashMap::new();
+        for e in edges.into_iter().filter(|e| keep_loops || !e.is_self_loop()) {
+            let pair = (e.src.min(e.dst), e.src.max(e.dst));
+            let w    = self.list[&e].clone();
+            match kept.get(&pair) {
+                Some(first) => {
+                    let merged = policy.merge(g.list[first].clone(), w);
+                    g.list.insert(*first, merged);
+                },
+                None => {
+                    kept.insert(pair, e);
+                    g.list.insert(e, w);
+                    for (name, values) in self.attrs.iter() {
+                        if let Some(value) = values.get(&e) {
+                            g.attrs.entry(name.clone()).or_default().insert(e, *value);
+                        }
+                    }
+                }
+            }
+        }
+        g
+    }
+
+    /// Returns the simple version of the graph: its self loops are removed
+    /// and the edges joining the same vertices (e.g. the anti-parallel arcs
+    /// of a digraph) are merged into one edge weighing what the policy says.
+    pub fn simplify(&self, policy: MergePolicy) -> Graph<W> {
+        let mut meta    = self.derived_meta("simplification");
+        meta.self_loops = false;
+        meta.oriented   = true;
+        self.merged(meta, policy, false)
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs as minimal whitespace separated edge lists,
+//! following the conventions of the SNAP datasets (also understood by the
+//! networkit loaders).
+
+use crate::Graph;
+use std::io::{self, Write};
+
+impl<T> Graph<T> {
+    /// Writes one `<src>\t<dst>` line per edge, preceded by `#` comment
+    /// lines describing the graph. The vertices are numbered following the
+    /// indexing base of the graph (see `GraphMeta::zero_indexed`).
+    pub fn to_snap(&self) -> String {
+        crate::written(|out| self.write_snap(out))
+    }
+
+    /// Same as `to_snap` but streams the output to the given writer
+    pub fn write_snap<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let gtype = if self.meta.digraph { "Directed graph" } else { "Undirected graph (each unordered pair of nodes is saved once)" };
+        writeln!(out, "# {}", gtype)?;
+        writeln!(out, "# Graph from {} generated w/ graph_gen: https://github.com/xgillard/graph_gen", self.meta.provenance)?;
+        writeln!(out, "# Nodes: {} Edges: {}", self.n, self.list.len())?;
+        writeln!(out, "# FromNodeId\tToNodeId")?;
+        for edge in self.list.keys() {
+            writeln!(out, "{}\t{}", self.index(edge.src), self.index(edge.dst))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+
+//! This module computes statistics on the graphs (e.g. their degree
+//! distribution), so that the generated instances can be checked right
+//! after they were generated.
+
+use crate::{Graph, Vertex};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+
+/// The summary statistics of a graph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphStats {
+    /// The number of vertices
+    pub n: usize,
+    /// The number of edges (self loops included)
+    pub m: usize,
+    /// The fraction of the pairs of distinct vertices (ordered pairs in a
+    /// digraph) which are joined by an edge
+    pub density: f64,
+    /// The minimum degree of the vertices
+    pub min_degree: usize,
+    /// The maximum degree of the vertices
+    pub max_degree: usize,
+    /// The mean degree of the vertices
+    pub mean_degree: f64,
+    /// The variance of the degrees of the vertices
+    pub degree_variance: f64,
+    /// The number of self loops
+    pub loops: usize,
+    /// The number of connected components (weakly connected components in a
+    /// digraph)
+    pub components: usize
+}
+
+/// Writes the statistics on a single line
+impl fmt::Display for GraphStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "n {}, m {}, density {:.4}, degree min {} max {} mean {:.3} variance {:.3}, loops {}, components {}",
+            self.n, self.m, self.density, self.min_degree, self.max_degree,
+            self.mean_degree, self.degree_variance, self.loops, self.components)
+    }
+}
+
+impl<W> Graph<W> {
+    /// Returns the degree histogram of the graph: the entry `d` gives the
+    /// number of vertices of degree `d` (see `degree`), up to the maximum
+    /// degree
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![];
+        for v in self.vertices() {
+            let d = self.degree(v);
+            if histogram.len() <= d {
+                histogram.resize(d + 1, 0);
+            }
+            histogram[d] += 1;
+        }
+        histogram
+    }
+    /// Returns the summary statistics of the graph (see `GraphStats`)
+    pub fn stats(&self) -> GraphStats {
+        let histogram = self.degree_histogram();
+        let n         = self.n.max(1) as f64;
+        // the sums start from 0.0 as an empty sum of floats is -0.0
+        let mean      = histogram.iter().enumerate().fold(0.0, |sum, (d, count)| sum + (d * count) as f64) / n;
+        let variance  = histogram.iter().enumerate().fold(0.0, |sum, (d, count)| sum + *count as f64 * (d as f64 - mean).powi(2)) / n;
+        let loops     = self.list.keys().filter(|e| e.is_self_loop()).count();
+        let pairs     = self.n as f64 * self.n.saturating_sub(1) as f64;
+        let pairs     = if self.meta.digraph { pairs } else { pairs / 2.0 };
+        GraphStats {
+            n              : self.n,
+            m              : self.list.len(),
+            density        : if pairs > 0.0 { (self.list.len() - loops) as f64 / pairs } else { 0.0 },
+            min_degree     : histogram.iter().position(|count| *count > 0).unwrap_or(0),
+            max_degree     : histogram.len().saturating_sub(1),
+            mean_degree    : mean,
+            degree_variance: variance,
+            loops,
+            components     : self.components().len()
+        }
+    }
+    /// Returns the local clustering coefficient of `v`: the fraction of the
+    /// pairs of neighbours of `v` which are adjacent themselves (0 when `v`
+    /// has fewer than two neighbours). The self loops and the orientation of
+    /// the arcs of a digraph are ignored.
+    pub fn local_clustering(&self, v: Vertex) -> f64 {
+        let (links, pairs) = self.wedges(v);
+        if pairs == 0 { 0.0 } else { links as f64 / pairs as f64 }
+    }
+    /// Returns the average of the local clustering coefficients of all the
+    /// vertices (see `local_clustering`)
+    pub fn average_clustering(&self) -> f64 {
+        let total = self.vertices().fold(0.0, |sum, v| sum + self.local_clustering(v));
+        total / self.n.max(1) as f64
+    }
+    /// Returns the global clustering coefficient (or transitivity) of the
+    /// graph: the fraction of the paths of length two which are closed by a
+    /// third edge, i.e. three times the number of triangles divided by the
+    /// number of connected triples (0 when there is no such path). The self
+    /// loops and the orientation of the arcs of a digraph are ignored.
+    pub fn global_clustering(&self) -> f64 {
+        let pairs = self.vertices()
+            .map(|v| self.neighbors(v).iter().filter(|u| **u != v).count())
+            .map(|k| k * k.saturating_sub(1) / 2)
+            .sum::<usize>();
+        if pairs == 0 { 0.0 } else { 3.0 * self.triangle_count() as f64 / pairs as f64 }
+    }
+    /// Returns the number of triangles of the graph (the self loops and the
+    /// orientation of the arcs of a digraph are ignored). Each edge is
+    /// oriented from its endpoint of lower degree towards the other one (ties
+    /// are broken by id), and the triangles are the common out-neighbours of
+    /// the endpoints of the edges: the sorted lists of these are intersected
+    /// in a single pass. This takes `O(m sqrt(m))` time.
+    pub fn triangle_count(&self) -> usize {
+        let rank    = |v: Vertex| (self.neighbors(v).len(), v);
+        let forward = self.vertices()
+            .map(|v| self.neighbors(v).iter().copied().filter(|u| rank(*u) > rank(v)).collect::<Vec<Vertex>>())
+            .collect::<Vec<Vec<Vertex>>>();
+        let mut triangles = 0;
+        for v in self.vertices() {
+            let out = &forward[v.id as usize - 1];
+            for u in out.iter() {
+                triangles += sorted_intersection(out, &forward[u.id as usize - 1]);
+            }
+        }
+        triangles
+    }
+    /// Returns the degree assortativity coefficient of the graph: the Pearson
+    /// correlation between the degrees of the endpoints of its edges (each
+    /// edge counts in both directions in an undirected graph). In a digraph,
+    /// it correlates the out degree of the source of each arc with the in
+    /// degree of its target. It ranges from -1 (the hubs are linked to the
+    /// leaves) to 1 (the vertices are linked to vertices of similar degree),
+    /// and is None when it is undefined: when the graph has no edge or when
+    /// all the degrees at the endpoints of the edges are equal (e.g. in a
+    /// regular graph).
+    pub fn degree_assortativity(&self) -> Option<f64> {
+        let pairs = self.list.keys().flat_map(|e| {
+            if self.meta.digraph {
+                vec![(self.out_degree(e.src) as f64, self.in_degree(e.dst) as f64)]
+            } else {
+                let (j, k) = (self.degree(e.src) as f64, self.degree(e.dst) as f64);
+                vec![(j, k), (k, j)]
+            }
+        }).collect::<Vec<(f64, f64)>>();
+        if pairs.is_empty() {
+            return None;
+        }
+        let m      = pairs.len() as f64;
+        let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / m;
+        let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / m;
+        let cov    = pairs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum::<f64>();
+        let var_x  = pairs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum::<f64>();
+        let var_y  = pairs.iter().map(|(_, y)| (y - mean_y).powi(2)).sum::<f64>();
+        if var_x == 0.0 || var_y == 0.0 {
+            None
+        } else {
+            Some(cov / (var_x * var_y).sqrt())
+        }
+    }
+    /// Returns the number of pairs of (distinct) neighbours of `v` which are
+    /// adjacent, and the number of pairs of neighbours of `v`
+    fn wedges(&self, v: Vertex) -> (usize, usize) {
+        let neighbours = self.neighbors(v).iter().copied().filter(|u| *u != v).collect::<Vec<Vertex>>();
+        let mut links  = 0;
+        for (i, a) in neighbours.iter().enumerate() {
+            let adjacent = self.neighbors(*a);
+            links += neighbours[i + 1..].iter().filter(|b| adjacent.binary_search(b).is_ok()).count();
+        }
+        let k = neighbours.len();
+        (links, k * k.saturating_sub(1) / 2)
+    }
+    /// Writes the statistics of the graph as a JSON document meant to be
+    /// stored next to the instance, so that benchmark suites describe
+    /// themselves: it gives the provenance of the graph (its model and
+    /// parameters), the seed it was generated with (if known) and the
+    /// statistics of the `write_stats` report (the diameter is null when the
+    /// graph is not connected).
+    pub fn write_stats_json<O: Write>(&self, mut out: O, seed: Option<u64>) -> io::Result<()> {
+        let stats      = self.stats();
+        let components = self.connected_components();
+        let strong     = self.strongly_connected_components().len();
+        let provenance = self.meta.provenance.replace('\\', "\\\\").replace('"', "\\\"");
+        let or_null    = |x: Option<String>| x.unwrap_or_else(|| "null".to_string());
+        let histogram  = self.degree_histogram().iter().map(|c| c.to_string()).collect::<Vec<String>>();
+        let sizes      = components.iter().map(|c| c.to_string()).collect::<Vec<String>>();
+        writeln!(out, "{{")?;
+        writeln!(out, r#"  "provenance": "{}","#, provenance)?;
+        writeln!(out, r#"  "directed": {},"#, self.meta.digraph)?;
+        writeln!(out, r#"  "seed": {},"#, or_null(seed.map(|s| s.to_string())))?;
+        writeln!(out, r#"  "stats": {{"#)?;
+        writeln!(out, r#"    "n": {},"#, stats.n)?;
+        writeln!(out, r#"    "m": {},"#, stats.m)?;
+        writeln!(out, r#"    "density": {},"#, stats.density)?;
+        writeln!(out, r#"    "min_degree": {},"#, stats.min_degree)?;
+        writeln!(out, r#"    "max_degree": {},"#, stats.max_degree)?;
+        writeln!(out, r#"    "mean_degree": {},"#, stats.mean_degree)?;
+        writeln!(out, r#"    "degree_variance": {},"#, stats.degree_variance)?;
+        writeln!(out, r#"    "loops": {},"#, stats.loops)?;
+        writeln!(out, r#"    "components": {},"#, stats.components)?;
+        writeln!(out, r#"    "component_sizes": [{}],"#, sizes.join(", "))?;
+        writeln!(out, r#"    "strong_components": {},"#, strong)?;
+        writeln!(out, r#"    "connected": {},"#, self.is_connected())?;
+        writeln!(out, r#"    "bipartite": {},"#, self.is_bipartite())?;
+        writeln!(out, r#"    "dag": {},"#, self.is_dag())?;
+        writeln!(out, r#"    "diameter": {},"#, or_null(self.diameter().map(|d| d.to_string())))?;
+        writeln!(out, r#"    "exact_diameter": {},"#, self.n <= Self::EXACT_DIAMETER_LIMIT)?;
+        writeln!(out, r#"    "triangles": {},"#, self.triangle_count())?;
+        writeln!(out, r#"    "global_clustering": {},"#, self.global_clustering())?;
+        writeln!(out, r#"    "average_clustering": {},"#, self.average_clustering())?;
+        writeln!(out, r#"    "degree_assortativity": {},"#, or_null(self.degree_assortativity().map(|r| r.to_string())))?;
+        writeln!(out, r#"    "degree_histogram": [{}]"#, histogram.join(", "))?;
+        writeln!(out, "  }}")?;
+        writeln!(out, "}}")
+    }
+    /// Writes a human readable report of the statistics of the graph: its
+    /// summary statistics (see `stats`), the number (and sizes) of the
+    /// connected components (and of the strongly connected components of a
+    /// digraph), whether it is connected, bipartite and acyclic, the
+    /// diameter, the number of triangles, the clustering coefficients, the
+    /// degree assortativity followed by the degree histogram (which omits the
+    /// degrees no vertex has)
+    pub fn write_stats<O: Write>(&self, mut out: O) -> io::Result<()> {
+        writeln!(out, "{}", self.stats())?;
+        let components = self.connected_components();
+        writeln!(out, "connected components: {} (sizes {})", components.len(), sizes(&components))?;
+        if self.meta.digraph {
+            let mut strong = self.strongly_connected_components().iter().map(|c| c.len()).collect::<Vec<usize>>();
+            strong.sort_unstable_by(|a, b| b.cmp(a));
+            writeln!(out, "strongly connected components: {} (sizes {})", strong.len(), sizes(&strong))?;
+        }
+        writeln!(out, "connected: {}, bipartite: {}, dag: {}", self.is_connected(), self.is_bipartite(), self.is_dag())?;
+        match self.diameter() {
+            None                                           => writeln!(out, "diameter: infinite (the graph is not connected)")?,
+            Some(d) if self.n > Self::EXACT_DIAMETER_LIMIT => writeln!(out, "diameter: at least {} (double sweep)", d)?,
+            Some(d)                                        => writeln!(out, "diameter: {}", d)?
+        }
+        writeln!(out, "triangles: {}", self.triangle_count())?;
+        writeln!(out, "clustering: global {:.4}, average local {:.4}", self.global_clustering(), self.average_clustering())?;
+        match self.degree_assortativity() {
+            None    => writeln!(out, "degree assortativity: undefined")?,
+            Some(r) => writeln!(out, "degree assortativity: {:.4}", r)?
+        }
+        writeln!(out, "degree histogram (degree: number of vertices)")?;
+        for (d, count) in self.degree_histogram().iter().enumerate().filter(|(_, count)| **count > 0) {
+            writeln!(out, "  {}: {}", d, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of vertices the two sorted lists have in common
+fn sorted_intersection(a: &[Vertex], b: &[Vertex]) -> usize {
+    let (mut i, mut j, mut common) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less    => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal   => { common += 1; i += 1; j += 1; }
+        }
+    }
+    common
+}
+
+/// Returns the given component sizes as a comma separated list, where the
+/// runs of equal sizes are written as `size x count`
+fn sizes(sizes: &[usize]) -> String {
+    let mut runs: Vec<(usize, usize)> = vec![];
+    for size in sizes.iter().copied() {
+        match runs.last_mut() {
+            Some((s, count)) if *s == size => *count += 1,
+            _ => runs.push((size, 1))
+        }
+    }
+    runs.iter()
+        .map(|(size, count)| if *count > 1 { format!("{} x {}", size, count) } else { size.to_string() })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,161 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module produces subgraph isomorphism benchmarks. Each instance is a
+//! pair pattern/target where the pattern is a random connected (induced)
+//! subgraph of the target. The embedding of the pattern in the target is
+//! recorded as a certificate. Both graphs are meant to be written in the LAD
+//! format (see `Graph::to_lad`).
+
+use crate::{Graph, GraphGenError, Vertex};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/// A subgraph isomorphism instance. The vertex `v` of the pattern is mapped
+/// onto the vertex `embedding[v-1]` of the target.
+#[derive(Debug, Clone)]
+pub struct SubIsoPair {
+    pattern  : Graph,
+    target   : Graph,
+    embedding: Vec<isize>
+}
+impl SubIsoPair {
+    /// Samples a random connected subgraph of (at most) `k` vertices from
+    /// the target graph and uses it as a pattern. The pattern has less than
+    /// `k` vertices when the target has no connected component that large.
+    /// It panics when the parameters are invalid (see `try_new`).
+    pub fn new(target: Graph, k: usize) -> Self {
+        Self::try_new(target, k).expect("invalid subgraph isomorphism parameters")
+    }
+    /// Same as `new` but fails when the pattern would have no vertex or more
+    /// vertices than the target
+    pub fn try_new(target: Graph, k: usize) -> Result<Self, GraphGenError> {
+        if k == 0 {
+            return Err(GraphGenError::NoVertex);
+        }
+        if k > target.n {
+            return Err(GraphGenError::Conflict("the pattern cannot have more vertices than the target"));
+        }
+        let mut rng = crate::random::rng();
+
+        let mut adj = vec![vec![]; target.n];
+        for edge in target.list.keys().filter(|e| !e.is_self_loop()) {
+            adj[edge.src.id as usize - 1].push(edge.dst.id);
+            adj[edge.dst.id as usize - 1].push(edge.src.id);
+        }
+
+        let mut starts = (1..=target.n as isize).collect::<Vec<isize>>();
+        starts.shuffle(&mut rng);
+
+        let mut embedding = vec![];
+        for start in starts {
+            let mut chosen   = vec![start];
+            let mut members  = HashSet::new();
+            let mut frontier = adj[start as usize - 1].clone();
+            members.insert(start);
+
+            while chosen.len() < k && !frontier.is_empty() {
+                let i = rng.gen_range(0, frontier.len());
+                let v = frontier.swap_remove(i);
+                if members.insert(v) {
+                    chosen.push(v);
+                    frontier.extend(adj[v as usize - 1].iter().filter(|u| !members.contains(u)));
+                }
+            }
+
+            if chosen.len() > embedding.len() {
+                embedding = chosen;
+            }
+            if embedding.len() >= k {
+                break;
+            }
+        }
+        embedding.truncate(k);
+        embedding.shuffle(&mut rng);
+
+        let vertices = embedding.iter().map(|id| Vertex{id: *id}).collect::<Vec<Vertex>>();
+        let pattern  = target.induced_subgraph(&vertices)
+            .expect("the embedding is made of distinct vertices of the target");
+
+        Ok(SubIsoPair{pattern, target, embedding})
+    }
+
+    pub fn pattern(&self) -> &Graph {
+        &self.pattern
+    }
+    pub fn target(&self) -> &Graph {
+        &self.target
+    }
+    /// Returns the certificate: one line `<pattern vertex> <target vertex>`
+    /// per vertex of the pattern. The vertices are numbered from zero (as in
+    /// the LAD format).
+    pub fn certificate(&self) -> String {
+        crate::written(|out| self.write_certificate(out))
+    }
+
+    /// Same as `certificate` but streams the output to the given writer
+    pub fn write_certificate<W: Write>(&self, mut out: W) -> io::Result<()> {
+        for (i, v) in self.embedding.iter().enumerate() {
+            writeln!(out, "{} {}", i, v - 1)?;
+        }
+        Ok(())
+    }
+
+    pub fn to_dimacs(&self) -> String {
+        crate::written(|out| self.write_dimacs(out))
+    }
+
+    /// Same as `to_dimacs` but streams the output to the given writer
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let gtype = if self.target.meta.digraph { "digraph" } else {"graph"};
+        writeln!(out, "c Pseudo-random subgraph isomorphism instance on a {} from {}", gtype, self.target.meta.provenance)?;
+        writeln!(out, "c The pattern is a connected induced subgraph with {} vertices and {} edges", self.pattern.n, self.pattern.list.len())?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Each graph starts with its '<vertices> <edges>' header")?;
+        writeln!(out, "c The embedding lines read 'm <pattern vertex> <target vertex>'")?;
+        writeln!(out, "c -------------------------------------------------------------")?;
+        writeln!(out, "c Generated w/ graph_gen: https://github.com/xgillard/graph_gen")?;
+
+        for (name, graph) in [("Pattern", &self.pattern), ("Target", &self.target)].iter() {
+            writeln!(out, "c {} graph", name)?;
+            writeln!(out, "{} {}", graph.n, graph.list.len())?;
+            for (edge, w) in graph.list.iter() {
+                writeln!(out, "{} {} {}", edge.src.id, edge.dst.id, w)?;
+            }
+        }
+
+        writeln!(out, "c Embedding")?;
+        for (i, v) in self.embedding.iter().enumerate() {
+            writeln!(out, "m {} {}", i + 1, v)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        crate::written(|out| self.write_dot(out))
+    }
+    pub fn write_dot<W: Write>(&self, mut out: W) -> io::Result<()> {
+        self.pattern.write_dot(&mut out)?;
+        self.target.write_dot(out)
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module writes graphs in the Trivial Graph Format (TGF) which can be
+//! imported in yEd.
+
+use crate::Graph;
+use std::fmt;
+use std::io::{self, Write};
+
+impl<T: fmt::Display> Graph<T> {
+    /// Writes the graph in TGF: one `<id> <label>` line per vertex (the
+    /// vertex id standing in for a missing label), then a `#` separator line
+    /// and one `<src> <dst> <weight>` line per edge (the weight being used as
+    /// the label of the edge).
+    pub fn to_tgf(&self) -> String {
+        crate::written(|out| self.write_tgf(out))
+    }
+
+    /// Same as `to_tgf` but streams the output to the given writer
+    pub fn write_tgf<W: Write>(&self, mut out: W) -> io::Result<()> {
+        for v in self.vertices() {
+            writeln!(out, "{} {}", self.index(v), self.label_or_id(v))?;
+        }
+        writeln!(out, "#")?;
+        for (edge, w) in self.list.iter() {
+            writeln!(out, "{} {} {}", self.index(edge.src), self.index(edge.dst), w)?;
+        }
+
+        Ok(())
+    }
+}
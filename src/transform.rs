@@ -0,0 +1,293 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module derives new graphs from existing ones (e.g. the complement of
+//! a graph). The derived graphs keep the digraph/self loops flags of the
+//! original one, and their provenance tells how they were obtained.
+
+use crate::{Edge, Graph, GraphMeta, Vertex};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+impl<W> Graph<W> {
+    /// Returns the metadata of a graph obtained by applying the given
+    /// transformation to this graph
+    pub(crate) fn derived_meta(&self, transformation: &str) -> GraphMeta {
+        GraphMeta {
+            provenance: format!("{} of {}", transformation, self.meta.provenance),
+            ..self.meta.clone()
+        }
+    }
+
+    /// Renumbers the vertices of the graph after a random permutation drawn
+    /// from the given rng (their labels, classes, weights, edges and planted
+    /// certificate follow them). It returns the permutation: the vertex `v`
+    /// became the vertex `permutation[v-1]`.
+    pub fn shuffle_labels<R: Rng>(&mut self, rng: &mut R) -> Vec<Vertex> {
+        let mut permutation = self.vertices().collect::<Vec<Vertex>>();
+        permutation.shuffle(rng);
+        let moved = |v: Vertex| permutation[v.id as usize - 1];
+
+        let labels = std::mem::take(&mut self.labels);
+        for v in self.vertices() {
+            labels.copy(v.id, &mut self.labels, moved(v).id);
+        }
+
+        let mut edges = std::mem::take(&mut self.list).into_iter().collect::<Vec<(Edge, W)>>();
+        edges.sort_unstable_by_key(|(e, _)| *e);
+        self.list = edges.into_iter()
+            .map(|(e, w)| (Edge{src: moved(e.src), dst: moved(e.dst)}, w))
+            .collect();
+        for values in self.attrs.values_mut() {
+            *values = values.iter()
+                .map(|(e, v)| (Edge{src: moved(e.src), dst: moved(e.dst)}, *v))
+                .collect();
+        }
+        self.adj = OnceLock::new();
+        self.certificate = self.certificate.as_ref().map(|c| c.renumbered(moved));
+
+        permutation
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    /// Copies the vertices (with their labels, classes and weights) and the
+    /// edges (with their weights and attributes) of this graph into `g`,
+    /// where the vertex `v` becomes the vertex `v + shift`
+    fn copy_into(&self, g: &mut Graph<W>, shift: isize) {
+        let moved = |v: Vertex| Vertex{id: v.id + shift};
+        for v in self.vertices() {
+            self.labels.copy(v.id, &mut g.labels, moved(v).id);
+        }
+        for (edge, w) in self.list.iter() {
+            g.list.insert(Edge{src: moved(edge.src), dst: moved(edge.dst)}, w.clone());
+        }
+        for (name, values) in self.attrs.iter() {
+            let copied = g.attrs.entry(name.clone()).or_default();
+            for (edge, value) in values.iter() {
+                copied.insert(Edge{src: moved(edge.src), dst: moved(edge.dst)}, *value);
+            }
+        }
+    }
+    /// Returns the metadata of the graph combining this graph and `other`
+    /// (an error is returned when only one of them is a digraph)
+    fn combined_meta(&self, other: &Graph<W>, combination: &str) -> Result<GraphMeta, String> {
+        if self.meta.digraph != other.meta.digraph {
+            return Err("cannot combine a digraph with an undirected graph".to_string());
+        }
+        Ok(GraphMeta {
+            provenance: format!("{} of {} and {}", combination, self.meta.provenance, other.meta.provenance),
+            self_loops: self.meta.self_loops || other.meta.self_loops,
+            ..self.meta.clone()
+        })
+    }
+
+    /// Returns the disjoint union of this graph and `other`: the vertices of
+    /// `other` are renumbered to come after those of this graph (its vertex
+    /// `v` becomes the vertex `v + self.num_vertices()`). The weights,
+    /// attributes, labels, classes and vertex weights are kept. An error is
+    /// returned when only one of the graphs is a digraph.
+    pub fn disjoint_union(&self, other: &Graph<W>) -> Result<Graph<W>, String> {
+        let mut g = Graph::empty(self.combined_meta(other, "disjoint union")?, self.n + other.n);
+        self.copy_into(&mut g, 0);
+        other.copy_into(&mut g, self.n as isize);
+        Ok(g)
+    }
+
+    /// Returns the subgraph induced by the given vertices: the vertex
+    /// `vertices[i]` becomes the vertex `i+1` of the subgraph (so that the
+    /// ids remain contiguous), and the subgraph has all the edges of this
+    /// graph (with their weights and attributes) between these vertices. The
+    /// labels, classes and weights of the vertices are kept as well. An error
+    /// is returned when a vertex does not belong to the graph or is given
+    /// twice.
+    pub fn induced_subgraph(&self, vertices: &[Vertex]) -> Result<Graph<W>, String> {
+        let mut position = HashMap::with_capacity(vertices.len());
+        for (i, v) in vertices.iter().enumerate() {
+            if v.id < 1 || v.id > self.n as isize {
+                return Err(format!("{} is not a vertex of the graph", v.id));
+            }
+            if position.insert(v.id, Vertex{id: i as isize + 1}).is_some() {
+                return Err(format!("the vertex {} is given twice", v.id));
+            }
+        }
+
+        let mut g = Graph::empty(self.derived_meta("induced subgraph"), vertices.len());
+        for (v, vertex) in vertices.iter().zip(g.vertices().collect::<Vec<Vertex>>()) {
+            self.labels.copy(v.id, &mut g.labels, vertex.id);
+        }
+        for (edge, w) in self.list.iter() {
+            if let (Some(src), Some(dst)) = (position.get(&edge.src.id), position.get(&edge.dst.id)) {
+                let kept = Edge{src: *src, dst: *dst};
+                g.list.insert(kept, w.clone());
+                for (name, values) in self.attrs.iter() {
+                    if let Some(value) = values.get(edge) {
+                        g.attrs.entry(name.clone()).or_default().insert(kept, *value);
+                    }
+                }
+            }
+        }
+        Ok(g)
+    }
+
+    /// Returns the k-core of the graph: its maximal induced subgraph where
+    /// each vertex has a degree of at least k (see `degree`). The vertices
+    /// of the core are renumbered as in `induced_subgraph` (in increasing id
+    /// order). The core may well be empty.
+    pub fn k_core(&self, k: usize) -> Graph<W> {
+        let mut degree  = self.vertices().map(|v| self.degree(v)).collect::<Vec<usize>>();
+        let mut removed = vec![false; self.n];
+        let mut pending = self.vertices().filter(|v| degree[v.id as usize - 1] < k).collect::<Vec<Vertex>>();
+
+        while let Some(v) = pending.pop() {
+            if removed[v.id as usize - 1] {
+                continue;
+            }
+            removed[v.id as usize - 1] = true;
+
+            let mut touched = self.out_neighbors(v).to_vec();
+            if self.meta.digraph {
+                touched.extend_from_slice(self.in_neighbors(v));
+            }
+            for u in touched.into_iter().filter(|u| *u != v && !removed[u.id as usize - 1]) {
+                degree[u.id as usize - 1] -= 1;
+                if degree[u.id as usize - 1] < k {
+                    pending.push(u);
+                }
+            }
+        }
+
+        let core  = self.vertices().filter(|v| !removed[v.id as usize - 1]).collect::<Vec<Vertex>>();
+        let mut g = self.induced_subgraph(&core).expect("the core is made of distinct vertices of the graph");
+        g.meta    = self.derived_meta(&format!("{}-core", k));
+        g
+    }
+
+    /// Returns the reverse (transpose) of the graph: every arc `u -> v` of
+    /// a digraph becomes the arc `v -> u` (with the same weight and
+    /// attributes). An undirected graph is its own reverse.
+    pub fn reverse(&self) -> Graph<W> {
+        let mut g = Graph::empty(self.derived_meta("reverse"), self.n);
+        g.labels  = self.labels.clone();
+        let flip  = |e: &Edge| if self.meta.digraph { e.rev() } else { *e };
+        for (edge, w) in self.list.iter() {
+            g.list.insert(flip(edge), w.clone());
+        }
+        for (name, values) in self.attrs.iter() {
+            g.attrs.insert(name.clone(), values.iter().map(|(e, v)| (flip(e), *v)).collect());
+        }
+        g
+    }
+}
+
+impl Graph {
+    /// Returns the join of this graph and `other`: their disjoint union (see
+    /// `disjoint_union`) where each vertex of this graph is also connected to
+    /// each vertex of `other` by an edge weighing 1 (by two opposite arcs in
+    /// a digraph).
+    pub fn join(&self, other: &Graph) -> Result<Graph, String> {
+        let mut g = Graph::empty(self.combined_meta(other, "join")?, self.n + other.n);
+        self.copy_into(&mut g, 0);
+        other.copy_into(&mut g, self.n as isize);
+        for u in self.vertices() {
+            for v in other.vertices() {
+                let v = Vertex{id: v.id + self.n as isize};
+                g.list.insert(Edge{src: u, dst: v}, 1);
+                if g.meta.digraph {
+                    g.list.insert(Edge{src: v, dst: u}, 1);
+                }
+            }
+        }
+        g.meta.oriented = false;
+        Ok(g)
+    }
+
+    /// Returns the complement of the graph: it has the same vertices (along
+    /// with their labels, classes and weights), and an edge (weighing 1)
+    /// between two vertices iff this graph has none. The complement only has
+    /// self loops when the model allows them.
+    pub fn complement(&self) -> Graph {
+        let mut meta  = self.derived_meta("complement");
+        meta.oriented = false;
+        let mut g     = Graph::empty(meta, self.n);
+        g.labels  = self.labels.clone();
+        for u in 1..=self.n as isize {
+            let first = if self.meta.digraph { 1 } else { u };
+            for v in first..=self.n as isize {
+                let edge = Edge{src: Vertex{id: u}, dst: Vertex{id: v}};
+                if edge.is_self_loop() && !self.meta.self_loops {
+                    continue;
+                }
+                if self.stored(edge).is_none() {
+                    g.list.insert(edge, 1);
+                }
+            }
+        }
+        g
+    }
+
+    /// Returns the line graph of this graph: each edge `u-v` becomes a vertex
+    /// (labeled `u-v`, the edges being taken in increasing order), and two of
+    /// these vertices are adjacent iff the corresponding edges share an end.
+    /// In a digraph, the arc `u->v` is connected to the arcs `v->w` instead
+    /// (the line digraph). The edges of the line graph weigh 1, and it has
+    /// no self loop.
+    pub fn line_graph(&self) -> Graph {
+        let mut edges = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+
+        let mut meta    = self.derived_meta("line graph");
+        meta.self_loops = false;
+        meta.oriented   = false;
+        let mut g       = Graph::empty(meta, edges.len());
+
+        // the (ids of the) edges leaving each vertex; or simply touching it
+        // in an undirected graph
+        let mut incident = vec![vec![]; self.n];
+        for (i, e) in edges.iter().enumerate() {
+            let id = i as isize + 1;
+            g.set_label(Vertex{id}, format!("{}-{}", self.index(e.src), self.index(e.dst)));
+            incident[e.src.id as usize - 1].push(id);
+            if !self.meta.digraph && !e.is_self_loop() {
+                incident[e.dst.id as usize - 1].push(id);
+            }
+        }
+
+        if self.meta.digraph {
+            for (i, e) in edges.iter().enumerate() {
+                let src = Vertex{id: i as isize + 1};
+                for j in incident[e.dst.id as usize - 1].iter().filter(|j| **j != src.id) {
+                    g.list.insert(Edge{src, dst: Vertex{id: *j}}, 1);
+                }
+            }
+        } else {
+            for touching in incident.iter() {
+                for (k, a) in touching.iter().enumerate() {
+                    for b in touching[k+1..].iter() {
+                        g.list.insert(Edge{src: Vertex{id: *a}, dst: Vertex{id: *b}}, 1);
+                    }
+                }
+            }
+        }
+        g
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module generates asymmetric traveling salesman (ATSP) instances.
+//! These are complete digraphs whose arc costs are drawn independently from
+//! one another (so the cost of `i -> j` is unrelated to that of `j -> i`).
+
+use rand::distributions::{Distribution, Uniform};
+use std::io::{self, Write};
+
+/// The cost written on the diagonal of the matrix (there is no such arc)
+const NO_ARC: isize = 9_999_999;
+
+/// A random asymmetric traveling salesman instance
+#[derive(Debug, Clone)]
+pub struct AtspInstance {
+    n    : usize,
+    costs: Vec<Vec<isize>>,
+    /// The seed the instance was generated with (if any)
+    seed : Option<u64>
+}
+impl AtspInstance {
+    /// Creates a new instance with `n` cities where the cost of each arc is
+    /// drawn uniformly at random in `1..=max_cost`
+    pub fn new(n: usize, max_cost: isize) -> Self {
+        let mut rng = crate::random::rng();
+        let dist    = Uniform::new_inclusive(1, max_cost.max(1));
+
+        let costs = (0..n).map(|i| (0..n)
+                .map(|j| if i == j { NO_ARC } else { dist.sample(&mut rng) })
+                .collect())
+            .collect();
+
+        AtspInstance{n, costs, seed: crate::random::thread_seed()}
+    }
+
+    /// Replaces the cost of each arc with one picked at random from the
+    /// given candidates
+    pub fn pluck_random_weights(&mut self, from: &[isize]) {
+        let mut rng = crate::random::weight_rng();
+        let dist= Uniform::new(0, from.len());
+
+        for (i, row) in self.costs.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                if i != j {
+                    *c = from[dist.sample(&mut rng)];
+                }
+            }
+        }
+    }
+
+    /// Writes the instance in the TSPLIB format (ATSP with an explicit
+    /// `FULL_MATRIX` of edge weights)
+    pub fn to_tsplib(&self) -> String {
+        crate::written(|out| self.write_tsplib(out))
+    }
+
+    /// Same as `to_tsplib` but streams the output to the given writer
+    pub fn write_tsplib<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "NAME: atsp{}", self.n)?;
+        writeln!(out, "TYPE: ATSP")?;
+        writeln!(out, "COMMENT: Pseudo-random asymmetric TSP with {} cities{} generated w/ graph_gen: https://github.com/xgillard/graph_gen", self.n, crate::random::seed_suffix(self.seed))?;
+        writeln!(out, "DIMENSION: {}", self.n)?;
+        writeln!(out, "EDGE_WEIGHT_TYPE: EXPLICIT")?;
+        writeln!(out, "EDGE_WEIGHT_FORMAT: FULL_MATRIX")?;
+        writeln!(out, "EDGE_WEIGHT_SECTION")?;
+        for row in self.costs.iter() {
+            writeln!(out, "{}", row.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(" "))?;
+        }
+        writeln!(out, "EOF")?;
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        crate::written(|out| self.write_dot(out))
+    }
+
+    /// Same as `to_dot` but streams the output to the given writer
+    pub fn write_dot<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "digraph atsp {{")?;
+        for v in 1..=self.n {
+            writeln!(out, "  {};", v)?;
+        }
+        for (i, row) in self.costs.iter().enumerate() {
+            for (j, c) in row.iter().enumerate() {
+                if i != j {
+                    writeln!(out, "  {} -> {} [label={}];", i + 1, j + 1, c)?;
+                }
+            }
+        }
+        writeln!(out, "}}")?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+
+//! This module post-processes the weights of the edges. It is mostly useful
+//! when deriving several variants of an instance from the same topology:
+//! the weights can be shuffled among the edges, rescaled into a range and
+//! rounded to integers, and a fraction of them can be negated. The weights
+//! can also be set after an embedding of the vertices in the plane so as to
+//! produce metric instances.
+
+use crate::{Edge, Graph, Point, Vertex, Weight};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+use std::ops::Neg;
+
+impl<W> Graph<W> {
+    /// Randomly permutes the weights (and leaves the topology untouched):
+    /// the multiset of the weights is preserved but each edge gets the
+    /// weight of some other edge.
+    pub fn shuffle_weights<R: Rng>(&mut self, rng: &mut R) {
+        let mut edges = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        let mut weights = edges.iter().map(|e| self.list.remove(e).expect("the edge belongs to the graph"))
+            .collect::<Vec<W>>();
+        weights.shuffle(rng);
+        self.list.extend(edges.into_iter().zip(weights));
+    }
+}
+
+impl<W> Graph<W> {
+    /// Sets the weight of each edge to `convert(d)` where `d` is the
+    /// euclidean distance between the positions of its ends
+    fn weigh_by_distance<F>(&mut self, positions: &[Point], convert: F) -> Result<(), String>
+        where F: Fn(f64) -> W
+    {
+        if positions.len() != self.n {
+            return Err(format!("{} positions are given for {} vertices", positions.len(), self.n));
+        }
+        let position = |v: Vertex| positions[v.id as usize - 1];
+        for (e, w) in self.list.iter_mut() {
+            *w = convert(position(e.src).distance(position(e.dst)));
+        }
+        Ok(())
+    }
+}
+
+impl Graph<f64> {
+    /// Sets the weight of each edge to the euclidean distance between its
+    /// ends, the vertex `v` being at `positions[v-1]`. An error is returned
+    /// when there is not exactly one position per vertex.
+    pub fn euclidean_weights(&mut self, positions: &[Point]) -> Result<(), String> {
+        self.weigh_by_distance(positions, |d| d)
+    }
+    /// Linearly rescales the weights so that the lightest edge weighs `lo`
+    /// and the heaviest one weighs `hi`. All weights become `lo` when they
+    /// are all equal.
+    pub fn rescale_weights(&mut self, lo: f64, hi: f64) {
+        let min = self.list.values().copied().fold(f64::INFINITY, f64::min);
+        let max = self.list.values().copied().fold(f64::NEG_INFINITY, f64::max);
+        for w in self.list.values_mut() {
+            *w = if max > min { lo + (*w - min) * (hi - lo) / (max - min) } else { lo };
+        }
+    }
+    /// Returns the same graph where the weights are rounded to integers
+    /// after `decimals` decimal digits have been kept: a weight `w` becomes
+    /// the integer closest to `w * 10^decimals` (hence `decimals = 0`
+    /// merely rounds the weights).
+    pub fn round_weights(&self, decimals: u32) -> Graph {
+        let factor = 10_f64.powi(decimals as i32);
+        self.map_weights(|_, w| (w * factor).round() as isize)
+    }
+}
+
+impl Graph {
+    /// Sets the weight of each edge to the euclidean distance between its
+    /// ends (rounded to the closest integer), the vertex `v` being at
+    /// `positions[v-1]`. An error is returned when there is not exactly one
+    /// position per vertex.
+    pub fn euclidean_weights(&mut self, positions: &[Point]) -> Result<(), String> {
+        self.weigh_by_distance(positions, |d| d.round() as isize)
+    }
+    /// Linearly rescales the weights so that the lightest edge weighs `lo`
+    /// and the heaviest one weighs `hi` (the other weights being rounded to
+    /// the closest integer). All weights become `lo` when they are all equal.
+    pub fn rescale_weights(&mut self, lo: isize, hi: isize) {
+        let mut g = self.map_weights(|_, w| *w as f64);
+        g.rescale_weights(lo as f64, hi as f64);
+        *self = g.round_weights(0);
+    }
+}
+
+impl<W: Weight + Neg<Output=W>> Graph<W> {
+    /// Negates the weight of each edge with the given probability and
+    /// returns the negated edges. An error is returned (and the graph is left
+    /// untouched) when the probability is not in [0, 1].
+    pub fn negate_weights<R: Rng>(&mut self, probability: f64, rng: &mut R) -> Result<Vec<Edge>, String> {
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(format!("the probability of a negated weight ({}) must be in [0, 1]", probability));
+        }
+        let mut edges = self.list.keys().copied().collect::<Vec<Edge>>();
+        edges.sort_unstable();
+        let negated = edges.into_iter().filter(|_| rng.gen_bool(probability)).collect::<Vec<Edge>>();
+        for e in negated.iter() {
+            let w = self.list[e];
+            self.list.insert(*e, -w);
+        }
+        Ok(negated)
+    }
+    /// Same as `negate_weights` but the digraph never gets a negative cycle
+    /// (e.g. for shortest paths experiments): as long as there is one, the
+    /// weight of one of its negated arcs is restored. An error is returned
+    /// when the graph is undirected (a negative edge is a negative cycle
+    /// there) or already had a negative cycle.
+    pub fn negate_weights_without_negative_cycle<R: Rng>(&mut self, probability: f64, rng: &mut R) -> Result<Vec<Edge>, String> {
+        if !self.meta.digraph {
+            return Err("only a digraph can have negative arcs and no negative cycle".to_string());
+        }
+        if self.negative_cycle().is_some() {
+            return Err("the digraph already has a negative cycle".to_string());
+        }
+        let mut negated = self.negate_weights(probability, rng)?.into_iter().collect::<HashSet<Edge>>();
+        while let Some(cycle) = self.negative_cycle() {
+            let restored = cycle.into_iter().find(|e| negated.contains(e))
+                .expect("only the negated arcs can close a negative cycle");
+            let w = self.list[&restored];
+            self.list.insert(restored, -w);
+            negated.remove(&restored);
+        }
+        let mut negated = negated.into_iter().collect::<Vec<Edge>>();
+        negated.sort_unstable();
+        Ok(negated)
+    }
+    /// Returns the arcs of a negative cycle of the digraph (if there is one),
+    /// found with the Bellman-Ford algorithm
+    fn negative_cycle(&self) -> Option<Vec<Edge>> {
+        let mut dist = vec![0.0; self.n];
+        let mut pred = vec![None; self.n];
+        let mut last = None;
+        for _ in 0..self.n {
+            last = None;
+            for (e, w) in self.list.iter() {
+                let (u, v) = (e.src.id as usize - 1, e.dst.id as usize - 1);
+                if dist[u] + w.to_f64() < dist[v] {
+                    dist[v] = dist[u] + w.to_f64();
+                    pred[v] = Some(*e);
+                    last    = Some(v);
+                }
+            }
+            last?;
+        }
+
+        // walking back n times from the last relaxed vertex lands on the cycle
+        let mut v = last?;
+        for _ in 0..self.n {
+            v = pred[v].expect("a relaxed vertex has a predecessor").src.id as usize - 1;
+        }
+        let mut cycle = vec![];
+        let mut u     = v;
+        loop {
+            let e = pred[u].expect("a vertex on the cycle has a predecessor");
+            cycle.push(e);
+            u = e.src.id as usize - 1;
+            if u == v {
+                return Some(cycle);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErModel, Graph, GraphMeta, Vertex};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn negating_every_arc_leaves_no_negative_cycle() {
+        let mut g   = ErModel::new(12, 0.5).digraph().generator_with_seed(4).gen();
+        let negated = g.negate_weights_without_negative_cycle(1.0, &mut StdRng::seed_from_u64(4)).unwrap();
+        assert!(!negated.is_empty());
+        assert!(g.negative_cycle().is_none());
+        assert!(negated.iter().all(|e| g.weight(*e) == Some(-1)));
+        assert_eq!(g.num_edges() - negated.len(), g.weighted_edges().filter(|(_, w)| *w > 0).count());
+    }
+
+    #[test]
+    fn one_arc_of_a_two_cycle_is_restored() {
+        let mut g = Graph::empty(GraphMeta::new("test").digraph(), 2);
+        g.add_edge(Vertex{id: 1}, Vertex{id: 2}, 1).unwrap();
+        g.add_edge(Vertex{id: 2}, Vertex{id: 1}, 1).unwrap();
+        let negated = g.negate_weights_without_negative_cycle(1.0, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(1, negated.len());
+        assert!(g.negative_cycle().is_none());
+    }
+
+    #[test]
+    fn a_negative_cycle_is_found() {
+        let mut g = Graph::empty(GraphMeta::new("test").digraph(), 4);
+        g.add_edge(Vertex{id: 1}, Vertex{id: 2}, 5).unwrap();
+        g.add_edge(Vertex{id: 2}, Vertex{id: 3}, -3).unwrap();
+        g.add_edge(Vertex{id: 3}, Vertex{id: 4}, 1).unwrap();
+        g.add_edge(Vertex{id: 4}, Vertex{id: 2}, 1).unwrap();
+        assert!(g.negative_cycle().is_some());
+        assert!(g.negate_weights_without_negative_cycle(0.5, &mut StdRng::seed_from_u64(1)).is_err());
+    }
+
+    #[test]
+    fn undirected_graphs_cannot_avoid_negative_cycles() {
+        let mut g = ErModel::new(5, 0.5).generator_with_seed(1).gen();
+        assert!(g.negate_weights_without_negative_cycle(0.5, &mut StdRng::seed_from_u64(1)).is_err());
+    }
+}